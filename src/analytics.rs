@@ -0,0 +1,100 @@
+//! Historical aggregate analytics over stored queries/test_results.
+//!
+//! `storage::ResultStore::join_results_for_query` answers "how did this one
+//! query do against the NIST battery"; `ResultStore::analytics_summary`
+//! answers the operator's question instead - "how are submitted generators
+//! doing, test-by-test and overall, over some time window". Percentiles and
+//! the p-value histogram are computed here in plain Rust rather than with
+//! per-dialect SQL (`PERCENTILE_CONT`, `width_bucket`, ...) so both backends
+//! can share one code path.
+
+use chrono::{DateTime, Utc};
+
+/// Pass/fail tally for a single NIST test across every query in the window.
+#[derive(Debug, Clone)]
+pub struct TestPassRate {
+    pub test_name: String,
+    pub total: i64,
+    pub passed: i64,
+    pub pass_rate: f64,
+}
+
+/// Count of p-values falling in `[bucket_start, bucket_start + 0.1)`.
+#[derive(Debug, Clone)]
+pub struct PValueBucket {
+    pub bucket_start: f64,
+    pub count: i64,
+}
+
+/// Dashboard-ready summary of everything logged in `[window_start, window_end)`.
+#[derive(Debug, Clone)]
+pub struct AnalyticsSummary {
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub total_queries: i64,
+    pub mean_quality_score: Option<f64>,
+    pub p50_quality_score: Option<f64>,
+    pub p95_quality_score: Option<f64>,
+    pub nist_used_count: i64,
+    pub mean_processing_time_ms: Option<f64>,
+    pub p95_processing_time_ms: Option<f64>,
+    pub test_pass_rates: Vec<TestPassRate>,
+    pub p_value_buckets: Vec<PValueBucket>,
+}
+
+/// Nearest-rank percentile of `sorted` (ascending), `pct` in `[0, 1]`.
+pub(crate) fn percentile(sorted: &[f64], pct: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((sorted.len() as f64) * pct).ceil() as usize;
+    let idx = rank.clamp(1, sorted.len()) - 1;
+    Some(sorted[idx])
+}
+
+/// Bucket p-values into 10 equal-width bins covering `[0, 1]`; a p-value of
+/// exactly 1.0 falls into the last bucket rather than overflowing an 11th.
+pub(crate) fn p_value_buckets(p_values: &[f64]) -> Vec<PValueBucket> {
+    let mut counts = [0i64; 10];
+    for &p in p_values {
+        let idx = ((p * 10.0).floor() as i64).clamp(0, 9) as usize;
+        counts[idx] += 1;
+    }
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| PValueBucket {
+            bucket_start: i as f64 / 10.0,
+            count,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_of_empty_slice_is_none() {
+        assert_eq!(percentile(&[], 0.5), None);
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let sorted = vec![10.0, 20.0, 30.0, 40.0, 50.0];
+        assert_eq!(percentile(&sorted, 0.5), Some(30.0));
+        assert_eq!(percentile(&sorted, 0.95), Some(50.0));
+        assert_eq!(percentile(&sorted, 0.01), Some(10.0));
+    }
+
+    #[test]
+    fn test_p_value_buckets_counts_and_edges() {
+        let p_values = vec![0.0, 0.05, 0.1, 0.55, 0.99, 1.0];
+        let buckets = p_value_buckets(&p_values);
+        assert_eq!(buckets.len(), 10);
+        assert_eq!(buckets[0].count, 2); // 0.0, 0.05
+        assert_eq!(buckets[1].count, 1); // 0.1
+        assert_eq!(buckets[5].count, 1); // 0.55
+        assert_eq!(buckets[9].count, 2); // 0.99, 1.0 (clamped into last bucket)
+    }
+}
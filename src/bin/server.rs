@@ -1,87 +1,364 @@
 use axum::{
-    extract::{ConnectInfo, Json, State},
-    http::HeaderMap,
-    response::{Html, IntoResponse},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, Json, Request, State,
+    },
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
     routing::{get, post},
     Router,
 };
-use randomnumbervalidator::{validate_random_numbers_full, ValidationRequest, ValidationResponse};
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use randomnumbervalidator::config::Config;
+use randomnumbervalidator::geoip::GeoIpDatabase;
+use randomnumbervalidator::job_queue::{self, JobQueue, JobQueueConfig};
+use randomnumbervalidator::storage::{
+    connect_with_settings, spawn_health_check_loop, PoolSettings, ResultStore, StorageConfig,
+};
+use randomnumbervalidator::{
+    apply_bit_selection, finish_validation, prepare_input_maybe_whitened, validate_against_distribution,
+    validate_random_numbers_full, BitSelection, NistTestResult, ValidationRequest, ValidationResponse,
+};
+use serde::Serialize;
 use std::net::SocketAddr;
-use std::time::Instant;
-use tower_http::{cors::CorsLayer, services::ServeDir, trace::TraceLayer};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceBuilder;
+use tower_http::{cors::CorsLayer, services::ServeDir, set_header::SetResponseHeaderLayer, trace::TraceLayer};
 use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+type SharedStore = Option<Arc<dyn ResultStore>>;
+
+/// Background batch job queue, shared the same way `SharedStore` is: both
+/// are entirely optional and degrade to "the feature is unavailable" rather
+/// than failing the server to start when `DATABASE_URL` isn't set.
+type SharedJobQueue = Option<Arc<dyn JobQueue>>;
+
+/// The GeoIP database is entirely optional, mirroring `SharedStore`: the
+/// server runs the same either way, just without a resolved `country` on
+/// logged queries when it's `None`.
+type SharedGeoIp = Option<Arc<GeoIpDatabase>>;
+
+/// In-memory cache of recent `ValidationResponse`s, keyed by a hash of the
+/// request fields that determine validation's outcome. `moka::sync::Cache`
+/// handles eviction (both the capacity bound and the TTL) internally, so
+/// this is just a thin type alias over it - see `cache_key` and
+/// `validate_handler`.
+type ResponseCache = moka::sync::Cache<u64, ValidationResponse>;
+
+/// State shared across every route: the optional result-storage backend and
+/// the response cache sit alongside each other rather than as two separate
+/// `State` extractors, since axum only supports one state type per router.
+#[derive(Clone)]
+struct AppState {
+    store: SharedStore,
+    cache: ResponseCache,
+    geoip: SharedGeoIp,
+    job_queue: SharedJobQueue,
+}
+
+/// Hash the request fields that determine validation's outcome - `numbers`,
+/// `input_format`, `range_min`, `range_max`, `bit_width`, `bit_order`,
+/// `use_whitening`, `packed_fields`, `bit_selection`, `with_calibration`, and
+/// `distribution_fit` (which, like `bit_width`, changes the response actually
+/// returned - here by adding a `distribution_fit` annotation - and so must
+/// also be part of the key) - into a cache key. Uses `blake3` purely for its
+/// speed and collision resistance here, not for any cryptographic property;
+/// `debug_log` is deliberately excluded since `validate_handler` bypasses the
+/// cache entirely for debug requests.
+fn cache_key(payload: &ValidationRequest) -> u64 {
+    let canonical = format!(
+        "{}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}",
+        payload.numbers,
+        payload.input_format,
+        payload.range_min,
+        payload.range_max,
+        payload.bit_width,
+        payload.bit_order,
+        payload.use_whitening,
+        payload.packed_fields,
+        payload.bit_selection,
+        payload.with_calibration,
+        payload.distribution_fit,
+    );
+    let digest = blake3::hash(canonical.as_bytes());
+    u64::from_le_bytes(digest.as_bytes()[..8].try_into().expect("blake3 digest is at least 8 bytes"))
+}
+
 #[tokio::main]
 async fn main() {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(errors) => {
+            eprintln!("Invalid server configuration:\n{}", errors);
+            std::process::exit(1);
+        }
+    };
+
     // Initialize tracing/logging
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "randomnumbervalidator=info,tower_http=info".into()),
+                .unwrap_or_else(|_| config.rust_log.clone().into()),
         )
         .with(tracing_subscriber::fmt::layer())
         .init();
 
     info!("Starting Random Number Validator server");
 
-    // Set up database connection pool (optional - will work without database)
-    let database_url = std::env::var("DATABASE_URL").ok();
-
-    let pool = if let Some(url) = database_url {
-        info!("Connecting to database...");
-        match PgPoolOptions::new().max_connections(5).connect(&url).await {
-            Ok(pool) => {
-                info!("Database connection established");
-                // Run migrations
-                match sqlx::migrate!("./migrations").run(&pool).await {
-                    Ok(_) => info!("Database migrations completed"),
-                    Err(e) => warn!("Failed to run migrations: {}", e),
+    // Set up result storage (optional - will work without it). A single
+    // DATABASE_URL switches between backends by its scheme: `postgres://...`
+    // talks to an external server, anything else (e.g. `sqlite://data.db` or
+    // `sqlite::memory:`) runs fully embedded, no server required.
+    let pool_settings = PoolSettings {
+        max_connections: config.db_max_connections,
+        ..PoolSettings::default()
+    };
+
+    let store: SharedStore = if let Some(url) = config.database_url.clone() {
+        let storage_config = if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            StorageConfig::Postgres(url)
+        } else {
+            StorageConfig::Sqlite(url)
+        };
+
+        info!("Connecting to storage backend...");
+        match connect_with_settings(storage_config, pool_settings.clone()).await {
+            Ok(store) => {
+                info!("Storage backend connected and migrated");
+                let store: Arc<dyn ResultStore> = Arc::from(store);
+                // `connect_with_settings` only proves the pool could open a
+                // connection to run migrations - it doesn't catch a pool
+                // that looks fine but can't actually serve a query (e.g. a
+                // permissions issue on the target database). Probe it for
+                // real before handing it out, so a silently-broken pool is
+                // downgraded here rather than on the first real insert.
+                match store.health_check().await {
+                    Ok(()) => Some(store),
+                    Err(e) => {
+                        warn!("Storage backend failed its startup health check: {}", e);
+                        warn!("Continuing without result logging");
+                        None
+                    }
                 }
-                Some(pool)
             }
             Err(e) => {
-                warn!("Failed to connect to database: {}", e);
-                warn!("Continuing without database logging");
+                warn!("Failed to connect to storage backend: {}", e);
+                warn!("Continuing without result logging");
+                None
+            }
+        }
+    } else {
+        info!("DATABASE_URL not set, result logging disabled");
+        None
+    };
+
+    // Periodically probe the backend so a pool that goes bad after startup
+    // (e.g. a database failover) shows up in logs before it shows up as
+    // request failures.
+    let health_check_handle = store
+        .clone()
+        .map(|store| spawn_health_check_loop(store, &pool_settings));
+
+    // The batch job queue shares `DATABASE_URL`'s scheme-based backend
+    // selection with result storage (see `store` above) rather than adding a
+    // second connection string to configure - `/api/validate/batch` and its
+    // worker loop are simply unavailable if it's unset.
+    let job_queue: SharedJobQueue = if let Some(url) = config.database_url.clone() {
+        let job_queue_config = if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            JobQueueConfig::Postgres(url)
+        } else {
+            JobQueueConfig::Sqlite(url)
+        };
+
+        match job_queue::connect(job_queue_config).await {
+            Ok(queue) => {
+                info!("Batch job queue connected and migrated");
+                Some(Arc::from(queue))
+            }
+            Err(e) => {
+                warn!("Failed to connect batch job queue: {}", e);
+                warn!("Continuing without batch job submission");
                 None
             }
         }
     } else {
-        info!("DATABASE_URL not set, database logging disabled");
         None
     };
 
-    // Allow configuring host via environment variable for Docker compatibility
-    let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_string());
-    let addr = format!("{}:{}", host, port);
+    // GeoIP is optional the same way result storage is: no GEOIP_DB_PATH (or
+    // a file that fails to load) just means logged queries keep a NULL
+    // `country`, not a failure to start.
+    let geoip: SharedGeoIp = match &config.geoip_db_path {
+        Some(path) => match GeoIpDatabase::open(path) {
+            Ok(db) => {
+                info!("GeoIP database loaded from {}", path.display());
+                Some(Arc::new(db))
+            }
+            Err(e) => {
+                warn!("{}", e);
+                warn!("Continuing without GeoIP country resolution");
+                None
+            }
+        },
+        None => {
+            info!("GEOIP_DB_PATH not set, GeoIP country resolution disabled");
+            None
+        }
+    };
+
+    let addr = format!("{}:{}", config.host, config.port);
 
     info!("Server listening on http://{}", addr);
     println!("Server running on http://{}", addr);
     println!("Set RUST_LOG=debug for detailed logging");
-    if pool.is_some() {
-        println!("Database logging enabled");
+    if store.is_some() {
+        println!("Result logging enabled");
     } else {
-        println!("Database logging disabled (set DATABASE_URL to enable)");
+        println!("Result logging disabled (set DATABASE_URL to enable)");
     }
 
+    let shutdown_store = store.clone();
+
+    // Worker loop needs its own handle on the queue/store/geoip independent
+    // of `state` (which is moved into the router below).
+    let job_worker_handle = job_queue
+        .clone()
+        .map(|queue| spawn_job_worker_loop(queue, store.clone(), geoip.clone()));
+
+    let cache: ResponseCache = moka::sync::Cache::builder()
+        .max_capacity(config.cache_max_capacity)
+        .time_to_live(config.cache_ttl)
+        .build();
+    let state = AppState { store, cache, geoip, job_queue };
+
+    // Static assets aren't content-hashed, so an updated file keeps its URL.
+    // The year-long `immutable` cache means a browser that already holds a
+    // copy doesn't revalidate it until max-age runs out; only then does
+    // ServeDir's Last-Modified/If-Modified-Since support pick up the change.
+    let static_service = ServiceBuilder::new()
+        .layer(SetResponseHeaderLayer::overriding(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=31536000, immutable"),
+        ))
+        .service(ServeDir::new("static"));
+
     let app = Router::new()
         .route("/", get(serve_index))
         .route("/game", get(serve_game))
         .route("/api/validate", post(validate_handler))
-        .nest_service("/static", ServeDir::new("static"))
+        .route("/api/validate/ws", get(validate_ws_handler))
+        .route("/api/validate/batch", post(submit_batch_handler))
+        .route("/health", get(health_handler))
+        .nest_service("/static", static_service)
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
-        .with_state(pool);
+        .layer(middleware::from_fn(security_headers))
+        .with_state(state);
 
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     axum::serve(
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
+    .with_graceful_shutdown(shutdown_signal())
     .await
     .unwrap();
+
+    // Stop probing and drain/close pooled connections before the runtime
+    // shuts down, rather than leaving tasks to hit a terminating executor.
+    if let Some(handle) = health_check_handle {
+        handle.abort();
+    }
+    if let Some(handle) = job_worker_handle {
+        handle.abort();
+    }
+    if let Some(store) = shutdown_store {
+        info!("Closing storage backend connections");
+        store.terminate().await;
+    }
+}
+
+/// Resolves once the process receives Ctrl+C (or, on Unix, SIGTERM), so
+/// `axum::serve` can stop accepting new connections and let in-flight ones
+/// finish before `main` tears down the storage pool.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Adds baseline HTTP hardening headers to every response, plus `no-store`
+/// on `/api/validate` so a validation result (which may depend on
+/// `cache_hit`/debug state) is never cached by an intermediary. Implemented
+/// as an `axum::middleware::from_fn` function rather than a hand-rolled
+/// `tower::Service` - post-processing a response is all this needs, and the
+/// repo otherwise only reaches for `tower_http`'s ready-made layers
+/// (`TraceLayer`, `CorsLayer`) rather than writing its own.
+///
+/// Skips `X-Frame-Options`/`Content-Security-Policy` on WebSocket upgrade
+/// requests, since `/api/validate/ws`'s `101 Switching Protocols` response
+/// isn't an HTML document a browser could frame or inject scripts into, and
+/// some reverse proxies are picky about extra headers on upgrade responses.
+async fn security_headers(request: Request, next: Next) -> Response {
+    let is_websocket_upgrade = is_websocket_upgrade_request(request.headers());
+    let is_validate_api = request.uri().path() == "/api/validate";
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+
+    headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    headers.insert(header::REFERRER_POLICY, HeaderValue::from_static("no-referrer-when-downgrade"));
+
+    if !is_websocket_upgrade {
+        headers.insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+        headers.insert(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("default-src 'self'; frame-ancestors 'none'"),
+        );
+    }
+
+    if is_validate_api {
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    }
+
+    response
+}
+
+/// A WebSocket upgrade request sets `Upgrade: websocket` and mentions
+/// `upgrade` in `Connection` - checked as a comma-separated list rather than
+/// an exact match, since a reverse proxy may rewrite it to
+/// `Connection: keep-alive, Upgrade`.
+fn is_websocket_upgrade_request(headers: &HeaderMap) -> bool {
+    let has_upgrade_header = headers
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let connection_mentions_upgrade = headers
+        .get(header::CONNECTION)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|part| part.trim().eq_ignore_ascii_case("upgrade")));
+
+    has_upgrade_header && connection_mentions_upgrade
 }
 
 async fn serve_index() -> impl IntoResponse {
@@ -114,8 +391,51 @@ async fn serve_game() -> impl IntoResponse {
     Html(html_with_version)
 }
 
+/// `GET /health` response, for container orchestrators and load balancers
+/// to readiness-probe the service.
+#[derive(Serialize)]
+struct HealthResponse {
+    status: &'static str,
+    database: DatabaseHealth,
+    git_hash: &'static str,
+    git_date: &'static str,
+}
+
+#[derive(Serialize)]
+struct DatabaseHealth {
+    connected: bool,
+    pool_size: u32,
+    in_use: u32,
+}
+
+async fn health_handler(State(state): State<AppState>) -> Json<HealthResponse> {
+    let database = match &state.store {
+        Some(store) => {
+            let connected = store.health_check().await.is_ok();
+            let status = store.pool_status();
+            DatabaseHealth {
+                connected,
+                pool_size: status.size,
+                in_use: status.in_use,
+            }
+        }
+        None => DatabaseHealth {
+            connected: false,
+            pool_size: 0,
+            in_use: 0,
+        },
+    };
+
+    Json(HealthResponse {
+        status: "ok",
+        database,
+        git_hash: env!("GIT_HASH"),
+        git_date: env!("GIT_DATE"),
+    })
+}
+
 async fn validate_handler(
-    State(pool): State<Option<PgPool>>,
+    State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     Json(payload): Json<ValidationRequest>,
@@ -137,15 +457,22 @@ async fn validate_handler(
         payload.numbers.split(',').count()
     );
 
-    // Perform validation (always uses NIST)
-    let response = validate_random_numbers_full(
-        &payload.numbers,
-        &payload.input_format,
-        payload.range_min,
-        payload.range_max,
-        payload.bit_width,
-        payload.debug_log,
-    );
+    // Debug runs always recompute - a debug log file is a side effect tied
+    // to this specific request, not something a cache hit should skip.
+    let key = cache_key(&payload);
+    let response = if !payload.debug_log {
+        if let Some(mut cached) = state.cache.get(&key) {
+            cached.cache_hit = true;
+            info!("Cache hit: query_id={}", query_id);
+            cached
+        } else {
+            let computed = run_validation(&payload);
+            state.cache.insert(key, computed.clone());
+            computed
+        }
+    } else {
+        run_validation(&payload)
+    };
     let processing_time_ms = start_time.elapsed().as_millis() as i32;
 
     // Log results
@@ -161,10 +488,11 @@ async fn validate_handler(
         );
     }
 
-    // Log to database if available
-    if let Some(pool) = pool {
-        if let Err(e) = log_query_to_database(
-            &pool,
+    // Log to storage if available
+    if let Some(store) = &state.store {
+        if let Err(e) = log_query_to_storage(
+            store.as_ref(),
+            state.geoip.as_deref(),
             query_id,
             &client_ip,
             user_agent,
@@ -174,13 +502,329 @@ async fn validate_handler(
         )
         .await
         {
-            error!("Failed to log query to database: {}", e);
+            error!("Failed to log query to storage: {}", e);
         }
     }
 
     Json(response)
 }
 
+/// Run the full NIST validation pipeline for `payload` (always uncached).
+fn run_validation(payload: &ValidationRequest) -> ValidationResponse {
+    validate_random_numbers_full(
+        &payload.numbers,
+        &payload.input_format,
+        payload.range_min,
+        payload.range_max,
+        payload.bit_width,
+        payload.bit_order,
+        payload.debug_log,
+        payload.use_whitening,
+        payload.packed_fields.as_deref(),
+        payload.bit_selection.as_ref(),
+        payload.with_calibration,
+        payload.distribution_fit.as_ref(),
+    )
+}
+
+/// Submit a `ValidationRequest` for asynchronous processing instead of
+/// blocking the caller on `/api/validate`: enqueues it via `job_queue` and
+/// returns its job id immediately, for `spawn_job_worker_loop`'s background
+/// worker to claim and run the NIST battery on later. Returns 503 if no
+/// `DATABASE_URL` is configured, the same way result logging degrades
+/// gracefully elsewhere in this file rather than failing the server to
+/// start.
+async fn submit_batch_handler(State(state): State<AppState>, Json(payload): Json<ValidationRequest>) -> Response {
+    let Some(queue) = &state.job_queue else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Batch job submission is not configured (set DATABASE_URL)",
+        )
+            .into_response();
+    };
+
+    match queue.enqueue(&payload).await {
+        Ok(job_id) => Json(serde_json::json!({ "job_id": job_id })).into_response(),
+        Err(e) => {
+            error!("Failed to enqueue batch job: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
+        }
+    }
+}
+
+/// Poll `queue` for visible jobs and run the full NIST validation pipeline
+/// on each, logging the result to `store` (if configured) and archiving the
+/// job when done - the asynchronous counterpart to `validate_handler`'s
+/// synchronous run-then-log. Mirrors `spawn_health_check_loop`'s
+/// `tokio::spawn` + abort-handle shape, but polls at a fixed interval
+/// instead of a fixed-period ticker, since there's no useful work between
+/// visible jobs.
+fn spawn_job_worker_loop(queue: Arc<dyn JobQueue>, store: SharedStore, geoip: SharedGeoIp) -> tokio::task::JoinHandle<()> {
+    const VISIBILITY_TIMEOUT_SECS: i64 = 300;
+    const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    tokio::spawn(async move {
+        loop {
+            let job = match queue.read(VISIBILITY_TIMEOUT_SECS).await {
+                Ok(Some(job)) => job,
+                Ok(None) => {
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+                Err(e) => {
+                    error!("Batch job queue read failed: {}", e);
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            info!("Processing batch job {}", job.msg_id);
+            let start_time = Instant::now();
+            let request = job.message.clone();
+            let response = match tokio::task::spawn_blocking(move || run_validation(&request)).await {
+                Ok(response) => response,
+                Err(e) => {
+                    error!("Batch job {} validation task panicked: {}", job.msg_id, e);
+                    ValidationResponse {
+                        valid: false,
+                        quality_score: 0.0,
+                        message: format!("Validation task panicked: {}", e),
+                        nist_results: None,
+                        nist_data: None,
+                        debug_file: None,
+                        cache_hit: false,
+                    }
+                }
+            };
+            let processing_time_ms = start_time.elapsed().as_millis() as i32;
+
+            if let Some(store) = &store {
+                let query_id = uuid::Uuid::new_v4();
+                if let Err(e) = log_query_to_storage(
+                    store.as_ref(),
+                    geoip.as_deref(),
+                    query_id,
+                    "batch-worker",
+                    "batch-worker",
+                    &job.message,
+                    &response,
+                    processing_time_ms,
+                )
+                .await
+                {
+                    error!("Failed to log batch job {} result to storage: {}", job.msg_id, e);
+                }
+            }
+
+            if let Err(e) = queue.archive(&job).await {
+                error!("Failed to archive batch job {}: {}", job.msg_id, e);
+            }
+        }
+    })
+}
+
+/// One frame of the `/api/validate/ws` protocol. The client sends a single
+/// JSON `ValidationRequest`; the server replies with a `started` frame
+/// carrying the total bit count, one `test_result` frame per `NistTestResult`,
+/// and a final `summary` frame - letting a UI render progress for inputs
+/// large enough that the full NIST battery takes seconds to finish.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WsValidationFrame<'a> {
+    Started { total_bits: usize },
+    TestResult { test: &'a NistTestResult },
+    Summary { valid: bool, quality_score: f64 },
+    Error { message: String },
+}
+
+/// Serialize `frame` and send it as a text frame. Returns `Err` if the
+/// socket is already gone, so callers can stop driving a dead connection
+/// instead of sending into it repeatedly.
+async fn send_ws_frame(socket: &mut WebSocket, frame: &WsValidationFrame<'_>) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(frame).expect("WsValidationFrame always serializes");
+    socket.send(Message::Text(text)).await
+}
+
+/// Upgrade to a WebSocket and stream validation progress instead of
+/// blocking until the whole `ValidationResponse` is ready. `WebSocketUpgrade`
+/// already checks the `Connection`/`Upgrade` headers itself (tolerant of the
+/// comma-joined `Connection: keep-alive, Upgrade` style a reverse proxy may
+/// rewrite them to), so `CorsLayer::permissive()` sitting in front of this
+/// route doesn't need any special-casing.
+async fn validate_ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let client_ip = extract_client_ip(&headers, addr);
+    let user_agent = headers
+        .get("user-agent")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("Unknown")
+        .to_string();
+
+    ws.on_failed_upgrade(|e| warn!("WebSocket upgrade failed: {}", e))
+        .on_upgrade(move |socket| handle_validate_ws(socket, state.store, state.geoip, client_ip, user_agent))
+}
+
+async fn handle_validate_ws(
+    mut socket: WebSocket,
+    store: SharedStore,
+    geoip: SharedGeoIp,
+    client_ip: String,
+    user_agent: String,
+) {
+    let request_text = match socket.recv().await {
+        Some(Ok(Message::Text(text))) => text,
+        Some(Ok(_)) | None => return,
+        Some(Err(e)) => {
+            warn!("WebSocket receive error: {}", e);
+            return;
+        }
+    };
+
+    let payload: ValidationRequest = match serde_json::from_str(&request_text) {
+        Ok(p) => p,
+        Err(e) => {
+            let _ = send_ws_frame(
+                &mut socket,
+                &WsValidationFrame::Error {
+                    message: format!("Invalid ValidationRequest: {}", e),
+                },
+            )
+            .await;
+            return;
+        }
+    };
+
+    let start_time = Instant::now();
+    let query_id = uuid::Uuid::new_v4();
+    info!(
+        "WS validation request received: query_id={}, ip={}",
+        query_id, client_ip
+    );
+
+    let bits = match prepare_input_maybe_whitened(
+        &payload.numbers,
+        &payload.input_format,
+        payload.range_min,
+        payload.range_max,
+        payload.bit_width,
+        payload.bit_order,
+        payload.use_whitening,
+        payload.packed_fields.as_deref(),
+    ) {
+        Ok(bits) => bits,
+        Err(e) => {
+            let _ = send_ws_frame(&mut socket, &WsValidationFrame::Error { message: e }).await;
+            return;
+        }
+    };
+
+    // Apply an optional `BitSelection` window (see
+    // `validate_random_numbers_full`'s matching step on the HTTP path)
+    // before streaming any frames, so `total_bits` below already reflects
+    // the windowed length rather than the full concatenation.
+    let bits = match &payload.bit_selection {
+        Some(selection) if *selection != BitSelection::All => match apply_bit_selection(&bits, selection) {
+            Ok(b) => b,
+            Err(e) => {
+                let _ = send_ws_frame(&mut socket, &WsValidationFrame::Error { message: e }).await;
+                return;
+            }
+        },
+        _ => bits,
+    };
+
+    if send_ws_frame(
+        &mut socket,
+        &WsValidationFrame::Started {
+            total_bits: bits.len(),
+        },
+    )
+    .await
+    .is_err()
+    {
+        return;
+    }
+
+    // Route through the same `finish_validation` tail the HTTP
+    // `/api/validate` path uses (instead of calling `NistWrapper::run_tests`
+    // directly), so a short input falls back to SP 800-90B min-entropy
+    // estimation here too rather than dead-ending in an `Error` frame the
+    // way the HTTP path never would for identical input. The NIST battery
+    // still runs as one internal pass with no per-test completion hook to
+    // stream from, so the `test_result` frames below are emitted in the
+    // order they appear in the finished battery rather than live as each one
+    // actually finishes. Running it via `spawn_blocking` still keeps this
+    // handler from stalling the executor for the seconds a large input takes.
+    let debug_log = payload.debug_log;
+    let with_calibration = payload.with_calibration;
+    let mut response = match tokio::task::spawn_blocking(move || finish_validation(bits, debug_log, with_calibration)).await {
+        Ok(response) => response,
+        Err(e) => {
+            let _ = send_ws_frame(
+                &mut socket,
+                &WsValidationFrame::Error {
+                    message: format!("NIST worker task panicked: {}", e),
+                },
+            )
+            .await;
+            return;
+        }
+    };
+
+    // Distribution-fit testing works on the raw numeric input rather than
+    // the assembled bitstream (see `validate_random_numbers_full`'s matching
+    // step on the HTTP path), so it's applied here too rather than inside
+    // `finish_validation`.
+    if let Some(distribution) = &payload.distribution_fit {
+        match validate_against_distribution(&payload.numbers, distribution) {
+            Ok(result) => response.distribution_fit = Some(result),
+            Err(e) => warn!("Skipping distribution-fit annotation: {}", e),
+        }
+    }
+
+    if let Some(nist_data) = &response.nist_data {
+        for test in &nist_data.individual_tests {
+            if send_ws_frame(&mut socket, &WsValidationFrame::TestResult { test }).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    let processing_time_ms = start_time.elapsed().as_millis() as i32;
+
+    let _ = send_ws_frame(
+        &mut socket,
+        &WsValidationFrame::Summary {
+            valid: response.valid,
+            quality_score: response.quality_score,
+        },
+    )
+    .await;
+
+    if let Some(store) = store {
+        if let Err(e) = log_query_to_storage(
+            store.as_ref(),
+            geoip.as_deref(),
+            query_id,
+            &client_ip,
+            &user_agent,
+            &payload,
+            &response,
+            processing_time_ms,
+        )
+        .await
+        {
+            error!("Failed to log WS query to storage: {}", e);
+        }
+    }
+
+    let _ = socket.send(Message::Close(None)).await;
+}
+
 /// Extract real client IP from headers (considering proxies) or fallback to socket address
 fn extract_client_ip(headers: &HeaderMap, addr: SocketAddr) -> String {
     // Check for common proxy headers in order of preference
@@ -203,16 +847,19 @@ fn extract_client_ip(headers: &HeaderMap, addr: SocketAddr) -> String {
     addr.ip().to_string()
 }
 
-/// Log query information to the database using the normalized schema
-async fn log_query_to_database(
-    pool: &PgPool,
+/// Log query information to storage using the normalized schema
+#[allow(clippy::too_many_arguments)]
+async fn log_query_to_storage(
+    store: &dyn ResultStore,
+    geoip: Option<&GeoIpDatabase>,
     query_id: uuid::Uuid,
     client_ip: &str,
     user_agent: &str,
     request: &ValidationRequest,
     response: &ValidationResponse,
     processing_time_ms: i32,
-) -> Result<(), sqlx::Error> {
+) -> Result<(), String> {
+    let country = geoip.and_then(|g| g.lookup_country(client_ip));
     // Prepare sample (first 5KB)
     const MAX_SAMPLE_SIZE: usize = 5 * 1024;
     let numbers_sample = if request.numbers.len() > MAX_SAMPLE_SIZE {
@@ -236,39 +883,26 @@ async fn log_query_to_database(
         total_numbers_count * 32 // Fallback estimate
     };
 
-    // Insert into queries table (NIST is always used now)
-    sqlx::query(
-        r#"
-        INSERT INTO queries (
-            query_id, created_at, client_ip, user_agent, country,
-            numbers_sample, numbers_truncated, total_numbers_count, total_bits_count,
-            valid, quality_score, nist_used,
-            processing_time_ms, error_message
-        ) VALUES (
-            $1, NOW(), $2, $3, NULL,
-            $4, $5, $6, $7,
-            $8, $9, true,
-            $10, NULL
+    store
+        .insert_query(
+            query_id,
+            client_ip,
+            user_agent,
+            country.as_deref(),
+            numbers_sample,
+            numbers_truncated,
+            total_numbers_count,
+            total_bits_count,
+            response.valid,
+            response.quality_score,
+            processing_time_ms,
         )
-        "#,
-    )
-    .bind(query_id)
-    .bind(client_ip)
-    .bind(user_agent)
-    .bind(numbers_sample)
-    .bind(numbers_truncated)
-    .bind(total_numbers_count)
-    .bind(total_bits_count)
-    .bind(response.valid)
-    .bind(response.quality_score)
-    .bind(processing_time_ms)
-    .execute(pool)
-    .await?;
+        .await?;
 
     // Insert individual test results if available
     if let Some(ref nist_data) = response.nist_data {
         for test_result in &nist_data.individual_tests {
-            if let Err(e) = log_test_result_to_database(pool, query_id, test_result).await {
+            if let Err(e) = store.insert_test_result(query_id, test_result).await {
                 warn!(
                     "Failed to log test result '{}' for query {}: {}",
                     test_result.name, query_id, e
@@ -277,61 +911,6 @@ async fn log_query_to_database(
         }
     }
 
-    info!("Query logged to database: query_id={}", query_id);
-    Ok(())
-}
-
-/// Log an individual test result to the database
-async fn log_test_result_to_database(
-    pool: &PgPool,
-    query_id: uuid::Uuid,
-    test_result: &randomnumbervalidator::NistTestResult,
-) -> Result<(), sqlx::Error> {
-    // First, ensure the test definition exists (get or create)
-    let test_id: i32 = sqlx::query_scalar(
-        r#"
-        INSERT INTO test_definitions (test_name, description)
-        VALUES ($1, $2)
-        ON CONFLICT (test_name) DO UPDATE SET test_name = EXCLUDED.test_name
-        RETURNING id
-        "#,
-    )
-    .bind(&test_result.name)
-    .bind(&test_result.description)
-    .fetch_one(pool)
-    .await?;
-
-    // Convert p_values Vec to JSON
-    let p_values_json = serde_json::to_value(&test_result.p_values)
-        .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
-
-    // Convert metrics Option<Vec<(String, String)>> to JSON
-    let metrics_json = if let Some(ref metrics) = test_result.metrics {
-        serde_json::to_value(metrics).map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-    } else {
-        serde_json::Value::Null
-    };
-
-    // Insert test result
-    sqlx::query(
-        r#"
-        INSERT INTO test_results (query_id, test_id, passed, p_value, p_values, metrics)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        ON CONFLICT (query_id, test_id) DO UPDATE SET
-            passed = EXCLUDED.passed,
-            p_value = EXCLUDED.p_value,
-            p_values = EXCLUDED.p_values,
-            metrics = EXCLUDED.metrics
-        "#,
-    )
-    .bind(query_id)
-    .bind(test_id)
-    .bind(test_result.passed)
-    .bind(test_result.p_value)
-    .bind(p_values_json)
-    .bind(metrics_json)
-    .execute(pool)
-    .await?;
-
+    info!("Query logged to storage: query_id={}", query_id);
     Ok(())
 }
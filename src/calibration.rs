@@ -0,0 +1,239 @@
+//! Monte-Carlo calibration of NIST pass-rate thresholds against trusted
+//! reference generators.
+//!
+//! Small Tier 1/Tier 2 inputs produce noisy p-values, so the absolute
+//! `alpha = 0.01` pass/fail gate can make even a cryptographically strong
+//! generator look marginal at a few hundred bits. This module runs the same
+//! tiered NIST battery (`nist_wrapper::NistWrapper`) many times against a
+//! cryptographically strong generator (ChaCha20) and a fast, non-cryptographic
+//! but statistically strong generator (PCG64), and records the empirical
+//! distribution of per-test p-values and overall pass counts at the
+//! requested size. Callers can then compare a user's result against this
+//! distribution instead of (or alongside) the absolute gate.
+//!
+//! This is deliberately *not* wired into `validate_random_numbers_full`'s hot
+//! path: a single calibration run means running the full NIST suite
+//! `2 * SAMPLES_PER_GENERATOR` times, which is fine for an on-demand
+//! "how good is a score of X at this size" query but far too expensive to
+//! repeat on every validation request.
+
+use std::collections::HashMap;
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rand_pcg::Pcg64;
+
+use crate::nist_wrapper::NistWrapper;
+use crate::NistResults;
+
+/// Reference sequences generated per generator per `calibrate()` call.
+const SAMPLES_PER_GENERATOR: usize = 100;
+
+/// Which reference generator a calibration sample was drawn from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ReferenceGenerator {
+    /// Cryptographically strong: ChaCha20.
+    ChaCha20,
+    /// Fast, statistically strong, non-cryptographic: PCG64.
+    Pcg64,
+}
+
+/// Empirical 1st/5th/median percentiles of a single NIST test's p-value,
+/// observed across many reference-generator runs at a given size.
+#[derive(Debug, Clone)]
+pub struct TestCalibration {
+    pub test_name: String,
+    pub p1: f64,
+    pub p5: f64,
+    pub median: f64,
+}
+
+/// Calibration result for a given bit count: per-test empirical p-value
+/// percentiles and the empirical distribution of overall pass counts, pooled
+/// across both reference generators.
+#[derive(Debug, Clone)]
+pub struct CalibrationResult {
+    pub bit_count: usize,
+    pub samples_per_generator: usize,
+    pub per_test: HashMap<String, TestCalibration>,
+    pub pass_count_p1: usize,
+    pub pass_count_p5: usize,
+    pub pass_count_median: usize,
+}
+
+impl CalibrationResult {
+    /// Where `tests_passed` (out of the same `total_tests` this calibration
+    /// ran) falls relative to the known-good pass-count distribution.
+    pub fn describe_pass_count(&self, tests_passed: usize) -> String {
+        if tests_passed <= self.pass_count_p1 {
+            format!(
+                "{} tests passed, at or below the 1st percentile ({}) of a known-good generator at {} bits",
+                tests_passed, self.pass_count_p1, self.bit_count
+            )
+        } else if tests_passed <= self.pass_count_p5 {
+            format!(
+                "{} tests passed, between the 1st ({}) and 5th ({}) percentile of a known-good generator at {} bits",
+                tests_passed, self.pass_count_p1, self.pass_count_p5, self.bit_count
+            )
+        } else {
+            format!(
+                "{} tests passed, at or above the 5th percentile ({}) of a known-good generator at {} bits (median {})",
+                tests_passed, self.pass_count_p5, self.bit_count, self.pass_count_median
+            )
+        }
+    }
+}
+
+/// Generate `bit_count` bits (the crate's 0/1-per-element bit vector
+/// convention) from the requested reference generator, seeded for
+/// reproducibility.
+fn generate_reference_bits(generator: ReferenceGenerator, seed: u64, bit_count: usize) -> Vec<u8> {
+    let byte_count = bit_count.div_ceil(8);
+    let mut bytes = vec![0u8; byte_count];
+    match generator {
+        ReferenceGenerator::ChaCha20 => ChaCha20Rng::seed_from_u64(seed).fill_bytes(&mut bytes),
+        ReferenceGenerator::Pcg64 => Pcg64::seed_from_u64(seed).fill_bytes(&mut bytes),
+    }
+
+    let mut bits = Vec::with_capacity(bit_count);
+    'bytes: for byte in bytes {
+        for i in (0..8).rev() {
+            if bits.len() == bit_count {
+                break 'bytes;
+            }
+            bits.push((byte >> i) & 1);
+        }
+    }
+    bits
+}
+
+/// Nearest-rank percentile of a value already sorted ascending.
+fn percentile_f64(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len());
+    sorted[rank - 1]
+}
+
+/// Nearest-rank percentile over a value already sorted ascending.
+fn percentile_usize(sorted: &[usize], pct: f64) -> usize {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((pct * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len());
+    sorted[rank - 1]
+}
+
+/// Run the tiered NIST battery on reference sequences from both ChaCha20 and
+/// PCG64 at `bit_count` bits, and record the empirical distribution of
+/// per-test p-values and overall pass counts.
+///
+/// `tier` must be the tier level (1-5) that `bit_count` actually falls
+/// into (see `nist_wrapper`'s tier thresholds); it's required explicitly, not
+/// derived silently, so a caller can't accidentally calibrate at the wrong
+/// size for the tier they think they're comparing against.
+pub fn calibrate(bit_count: usize, tier: u8) -> Result<CalibrationResult, String> {
+    let actual_tier = NistWrapper::tier_level_for_bit_count(bit_count);
+    if actual_tier != tier {
+        return Err(format!(
+            "bit_count {} corresponds to tier {}, not the requested tier {}",
+            bit_count, actual_tier, tier
+        ));
+    }
+    calibrate_with_samples(bit_count, SAMPLES_PER_GENERATOR)
+}
+
+fn calibrate_with_samples(
+    bit_count: usize,
+    samples_per_generator: usize,
+) -> Result<CalibrationResult, String> {
+    let wrapper = NistWrapper::new();
+    let mut p_values_by_test: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut pass_counts: Vec<usize> = Vec::with_capacity(samples_per_generator * 2);
+
+    for generator in [ReferenceGenerator::ChaCha20, ReferenceGenerator::Pcg64] {
+        for seed in 0..samples_per_generator as u64 {
+            let bits = generate_reference_bits(generator, seed, bit_count);
+            let results: NistResults = wrapper.run_tests(&bits)?;
+            pass_counts.push(results.tests_passed);
+            for test in &results.individual_tests {
+                p_values_by_test
+                    .entry(test.name.clone())
+                    .or_default()
+                    .push(test.p_value);
+            }
+        }
+    }
+
+    let per_test = p_values_by_test
+        .into_iter()
+        .map(|(name, mut p_values)| {
+            p_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let calibration = TestCalibration {
+                test_name: name.clone(),
+                p1: percentile_f64(&p_values, 0.01),
+                p5: percentile_f64(&p_values, 0.05),
+                median: percentile_f64(&p_values, 0.5),
+            };
+            (name, calibration)
+        })
+        .collect();
+
+    pass_counts.sort_unstable();
+
+    Ok(CalibrationResult {
+        bit_count,
+        samples_per_generator,
+        per_test,
+        pass_count_p1: percentile_usize(&pass_counts, 0.01),
+        pass_count_p5: percentile_usize(&pass_counts, 0.05),
+        pass_count_median: percentile_usize(&pass_counts, 0.5),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibrate_rejects_mismatched_tier() {
+        let result = calibrate(1_000, 1);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("tier"));
+    }
+
+    #[test]
+    fn test_calibrate_with_samples_covers_tier_1() {
+        // Use a small sample count so this test runs quickly, and Tier 1's
+        // minimum bit count (100) directly, since `TestTier` is private to
+        // `nist_wrapper`. The statistics themselves aren't asserted
+        // precisely, just that the machinery produces sane, fully-populated
+        // results.
+        let result = calibrate_with_samples(100, 5).unwrap();
+        assert_eq!(result.bit_count, 100);
+        assert_eq!(result.samples_per_generator, 5);
+        assert!(!result.per_test.is_empty());
+        assert!(result.pass_count_p1 <= result.pass_count_p5);
+        assert!(result.pass_count_p5 <= result.pass_count_median);
+        for calibration in result.per_test.values() {
+            assert!(calibration.p1 <= calibration.p5);
+            assert!(calibration.p5 <= calibration.median);
+        }
+    }
+
+    #[test]
+    fn test_describe_pass_count_buckets() {
+        let result = CalibrationResult {
+            bit_count: 1_000,
+            samples_per_generator: 100,
+            per_test: HashMap::new(),
+            pass_count_p1: 5,
+            pass_count_p5: 7,
+            pass_count_median: 9,
+        };
+        assert!(result.describe_pass_count(3).contains("1st percentile"));
+        assert!(result.describe_pass_count(6).contains("between"));
+        assert!(result.describe_pass_count(9).contains("above"));
+    }
+}
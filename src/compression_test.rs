@@ -0,0 +1,129 @@
+//! Compression-ratio incompressibility check, complementing the NIST suite.
+//!
+//! A truly random bit stream is essentially incompressible; visibly
+//! structured output (repeating patterns, low per-symbol entropy) shrinks
+//! under even a simple dictionary coder. This runs a self-contained LZ78-style
+//! coder over the packed bytes and reports how much it shrank - a cheap catch
+//! for structure some of the p-value-based NIST tests miss on short inputs.
+//! See `min_entropy::compression_entropy` for this same idea applied as a
+//! bits-per-symbol estimator instead of a pass/fail ratio.
+
+use std::collections::HashMap;
+
+/// Below this many packed bytes, the LZ78 dictionary's own bootstrap cost
+/// dominates the result and a low ratio isn't meaningful evidence of
+/// structure - skip the test rather than report a false positive.
+pub const MIN_BYTES_FOR_COMPRESSION_TEST: usize = 256; // a few kilobits packed
+
+/// A compressed size at or above this fraction of the packed size is
+/// considered incompressible enough to pass.
+pub const PASS_RATIO_THRESHOLD: f64 = 0.95;
+
+/// Result of running the incompressibility check over a packed byte stream.
+#[derive(Debug, Clone)]
+pub struct CompressionResult {
+    pub original_len: usize,
+    pub compressed_len: usize,
+    pub ratio: f64,
+    pub passed: bool,
+}
+
+/// Run an LZ78-style dictionary coder over `bytes` and report how much it
+/// shrank. Returns `None` when `bytes` is shorter than
+/// `MIN_BYTES_FOR_COMPRESSION_TEST`, since the test isn't meaningful there.
+pub fn compression_test(bytes: &[u8]) -> Option<CompressionResult> {
+    if bytes.len() < MIN_BYTES_FOR_COMPRESSION_TEST {
+        return None;
+    }
+
+    let compressed_len = lz78_compressed_len(bytes);
+    let ratio = compressed_len as f64 / bytes.len() as f64;
+
+    Some(CompressionResult {
+        original_len: bytes.len(),
+        compressed_len,
+        ratio,
+        passed: ratio >= PASS_RATIO_THRESHOLD,
+    })
+}
+
+/// Estimate the compressed size (in bytes) of `bytes` under a simple LZ78
+/// dictionary coder: each phrase is encoded as (dictionary index, literal
+/// byte), costing `ceil(log2(dictionary_size + 1)) + 8` bits. A generator
+/// with low entropy repeats itself, so it needs far fewer, shorter phrases
+/// than a random stream does.
+fn lz78_compressed_len(bytes: &[u8]) -> usize {
+    let mut dictionary: HashMap<Vec<u8>, u32> = HashMap::new();
+    let mut next_index: u32 = 1; // 0 is reserved for "no prefix"
+    let mut current: Vec<u8> = Vec::new();
+    let mut total_bits: u64 = 0;
+
+    for &byte in bytes {
+        current.push(byte);
+        if !dictionary.contains_key(&current) {
+            total_bits += index_bits(next_index) + 8;
+            dictionary.insert(current.clone(), next_index);
+            next_index += 1;
+            current.clear();
+        }
+    }
+    if !current.is_empty() {
+        total_bits += index_bits(next_index) + 8;
+    }
+
+    total_bits.div_ceil(8) as usize
+}
+
+/// Bits needed to reference any of `dictionary_size` phrases.
+fn index_bits(dictionary_size: u32) -> u64 {
+    (32 - dictionary_size.leading_zeros()).max(1) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_test_requires_minimum_size() {
+        let bytes = vec![0u8; MIN_BYTES_FOR_COMPRESSION_TEST - 1];
+        assert!(compression_test(&bytes).is_none());
+    }
+
+    #[test]
+    fn test_constant_bytes_compress_well_and_fail() {
+        let bytes = vec![0u8; MIN_BYTES_FOR_COMPRESSION_TEST * 4];
+        let result = compression_test(&bytes).expect("input meets the minimum size");
+        assert!(
+            result.ratio < PASS_RATIO_THRESHOLD,
+            "constant bytes should compress far below the pass threshold, got ratio {}",
+            result.ratio
+        );
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn test_varied_bytes_are_incompressible_and_pass() {
+        // A simple non-repeating byte stream should resist compression - not
+        // cryptographically random, but varied enough to stay near 1.0.
+        let bytes: Vec<u8> = (0..MIN_BYTES_FOR_COMPRESSION_TEST * 4)
+            .map(|i| ((i * 2654435761u64) % 256) as u8)
+            .collect();
+        let result = compression_test(&bytes).expect("input meets the minimum size");
+        assert!(
+            result.ratio >= PASS_RATIO_THRESHOLD,
+            "varied bytes should resist compression, got ratio {}",
+            result.ratio
+        );
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_ratio_matches_compressed_over_original_len() {
+        let bytes: Vec<u8> = (0..MIN_BYTES_FOR_COMPRESSION_TEST * 2)
+            .map(|i| (i % 7) as u8)
+            .collect();
+        let result = compression_test(&bytes).unwrap();
+        assert_eq!(result.original_len, bytes.len());
+        assert!((result.ratio - result.compressed_len as f64 / result.original_len as f64).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,131 @@
+//! Typed server configuration, parsed once at startup.
+//!
+//! Centralizes what used to be scattered `std::env::var(...).unwrap_or_else(...)`
+//! calls in `server.rs`'s `main()` into one place that validates everything up
+//! front and fails fast with every problem reported together, rather than
+//! surfacing the first bad value as a runtime panic on `listener.bind` (or
+//! worse, a silently-wrong default) deep into startup.
+
+use std::env;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Validated server configuration, assembled once via [`Config::load`].
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub host: IpAddr,
+    pub port: u16,
+    pub database_url: Option<String>,
+    pub rust_log: String,
+    pub db_max_connections: u32,
+    pub cache_max_capacity: u64,
+    pub cache_ttl: Duration,
+    pub geoip_db_path: Option<PathBuf>,
+}
+
+impl Config {
+    /// Load a `RUST_ENV`-selected `.env` file (if any), then parse every
+    /// setting from the environment. On a validation failure for one or more
+    /// fields, returns every failure joined into one message - each line
+    /// names the env var and what values it accepts - rather than stopping
+    /// at the first bad one.
+    pub fn load() -> Result<Config, String> {
+        load_dotenv();
+
+        let mut errors: Vec<String> = Vec::new();
+
+        let host = parse_var_or_default(&mut errors, "HOST", "0.0.0.0", |v| {
+            v.parse::<IpAddr>().map_err(|_| {
+                format!(
+                    "must be a valid IP address (e.g. '0.0.0.0' or '127.0.0.1'), got '{}'",
+                    v
+                )
+            })
+        });
+
+        let port = parse_var_or_default(&mut errors, "PORT", "3000", |v| {
+            v.parse::<u16>()
+                .map_err(|_| format!("must be an integer between 0 and 65535, got '{}'", v))
+        });
+
+        // Default to one connection per CPU rather than a fixed number, so
+        // the pool scales with the machine it's deployed on instead of
+        // silently bottlenecking on a beefier host.
+        let cpu_count_default = num_cpus::get().to_string();
+        let db_max_connections = parse_var_or_default(&mut errors, "DB_MAX_CONNECTIONS", &cpu_count_default, |v| {
+            match v.parse::<u32>() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(format!("must be a positive integer, got '{}'", v)),
+            }
+        });
+
+        let cache_max_capacity = parse_var_or_default(&mut errors, "CACHE_MAX_CAPACITY", "10000", |v| {
+            v.parse::<u64>()
+                .map_err(|_| format!("must be a non-negative integer, got '{}'", v))
+        });
+
+        let cache_ttl_secs = parse_var_or_default(&mut errors, "CACHE_TTL_SECS", "3600", |v| {
+            v.parse::<u64>()
+                .map_err(|_| format!("must be a non-negative integer (seconds), got '{}'", v))
+        });
+
+        let database_url = env::var("DATABASE_URL").ok();
+        let rust_log = env::var("RUST_LOG")
+            .unwrap_or_else(|_| "randomnumbervalidator=info,tower_http=info".to_string());
+        let geoip_db_path = env::var("GEOIP_DB_PATH").ok().map(PathBuf::from);
+
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+
+        Ok(Config {
+            host,
+            port,
+            database_url,
+            rust_log,
+            db_max_connections,
+            cache_max_capacity,
+            cache_ttl: Duration::from_secs(cache_ttl_secs),
+            geoip_db_path,
+        })
+    }
+}
+
+/// Load `.env.{RUST_ENV}` when `RUST_ENV` is set (e.g. `.env.production`),
+/// falling back to plain `.env` otherwise. Either file is optional - a
+/// missing file just means settings come from the process environment
+/// alone, same as today.
+fn load_dotenv() {
+    let env_file = match env::var("RUST_ENV") {
+        Ok(rust_env) => format!(".env.{}", rust_env),
+        Err(_) => ".env".to_string(),
+    };
+    if !std::path::Path::new(&env_file).exists() {
+        return; // no file to load - settings come from the environment alone
+    }
+    match dotenvy::from_filename(&env_file) {
+        Ok(_) => tracing::debug!("Loaded environment from {}", env_file),
+        Err(e) => tracing::warn!("Failed to load {}: {}", env_file, e),
+    }
+}
+
+/// Read env var `name`, falling back to `default` when unset, and parse the
+/// result with `parse`. A parse failure is recorded in `errors` (so every
+/// bad setting is reported together) and `default` is parsed again as the
+/// returned value, since the default is trusted to always be valid.
+fn parse_var_or_default<T>(
+    errors: &mut Vec<String>,
+    name: &str,
+    default: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> T {
+    let raw = env::var(name).unwrap_or_else(|_| default.to_string());
+    match parse(&raw) {
+        Ok(value) => value,
+        Err(reason) => {
+            errors.push(format!("{}: {}", name, reason));
+            parse(default).unwrap_or_else(|_| panic!("default value for {} failed its own validation", name))
+        }
+    }
+}
@@ -0,0 +1,472 @@
+//! Goodness-of-fit testing against a declared (typically non-uniform)
+//! target distribution, operating on parsed numeric values rather than
+//! their bit-level encoding. This complements the NIST SP 800-22 suite,
+//! which only ever asks "are these bits uniform/random" — a generator that
+//! is supposed to sample from, say, a Gaussian or exponential distribution
+//! needs a different kind of check entirely.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::enhanced_stats::StatisticalTestResult;
+use crate::special_functions::{erfc, igamc, log_gamma};
+
+/// A target distribution and its parameters, declared by the caller (not
+/// estimated from the sample).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TargetDistribution {
+    Uniform { a: f64, b: f64 },
+    Normal { mean: f64, std_dev: f64 },
+    Exponential { lambda: f64 },
+}
+
+impl TargetDistribution {
+    /// Number of free parameters, used as the chi-square degrees-of-freedom penalty.
+    fn param_count(&self) -> usize {
+        match self {
+            TargetDistribution::Uniform { .. } => 2,
+            TargetDistribution::Normal { .. } => 2,
+            TargetDistribution::Exponential { .. } => 1,
+        }
+    }
+
+    /// Cumulative distribution function `F(x)`.
+    fn cdf(&self, x: f64) -> f64 {
+        match *self {
+            TargetDistribution::Uniform { a, b } => ((x - a) / (b - a)).clamp(0.0, 1.0),
+            TargetDistribution::Normal { mean, std_dev } => {
+                0.5 * erfc(-(x - mean) / (std_dev * std::f64::consts::SQRT_2))
+            }
+            TargetDistribution::Exponential { lambda } => {
+                if x <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - (-lambda * x).exp()
+                }
+            }
+        }
+    }
+}
+
+/// Number of equal-probability bins used by the chi-square test.
+const CHI_SQUARE_BIN_COUNT: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChiSquareResult {
+    pub bin_count: usize,
+    pub degrees_of_freedom: usize,
+    pub statistic: f64,
+    pub p_value: f64,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KolmogorovSmirnovResult {
+    pub statistic: f64,
+    pub critical_value: f64,
+    pub p_value: f64,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionFitResult {
+    pub sample_count: usize,
+    pub chi_square: ChiSquareResult,
+    pub kolmogorov_smirnov: KolmogorovSmirnovResult,
+}
+
+/// Test whether `samples` is consistent with `distribution`, via both a
+/// chi-square goodness-of-fit test and a Kolmogorov-Smirnov test.
+pub fn fit(
+    samples: &[f64],
+    distribution: &TargetDistribution,
+) -> Result<DistributionFitResult, String> {
+    let n = samples.len();
+    if n < 5 {
+        return Err("Distribution fit testing requires at least 5 samples".to_string());
+    }
+
+    Ok(DistributionFitResult {
+        sample_count: n,
+        chi_square: chi_square_test(samples, distribution),
+        kolmogorov_smirnov: kolmogorov_smirnov_test(samples, distribution),
+    })
+}
+
+/// Chi-square goodness-of-fit test. Samples are transformed via the
+/// probability integral transform `u = F(x)` (uniform on `[0,1]` under the
+/// null hypothesis) and binned into `k` equal-width, equal-probability
+/// bins, so `E_i = n/k` for every bin regardless of the target
+/// distribution's shape.
+fn chi_square_test(samples: &[f64], distribution: &TargetDistribution) -> ChiSquareResult {
+    let n = samples.len();
+    let k = CHI_SQUARE_BIN_COUNT.min(n).max(2);
+    let mut bins = vec![0usize; k];
+
+    for &x in samples {
+        let u = distribution.cdf(x).clamp(0.0, 1.0 - f64::EPSILON);
+        let idx = ((u * k as f64).floor() as usize).min(k - 1);
+        bins[idx] += 1;
+    }
+
+    let expected = n as f64 / k as f64;
+    let statistic: f64 = bins
+        .iter()
+        .map(|&o| {
+            let diff = o as f64 - expected;
+            diff * diff / expected
+        })
+        .sum();
+
+    let degrees_of_freedom = k.saturating_sub(1).saturating_sub(distribution.param_count());
+    let p_value = if degrees_of_freedom > 0 {
+        igamc(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+    } else {
+        1.0
+    };
+
+    ChiSquareResult {
+        bin_count: k,
+        degrees_of_freedom,
+        statistic,
+        p_value,
+        passed: p_value >= 0.01,
+    }
+}
+
+/// Kolmogorov-Smirnov test: `D = max_i max(|i/n - F(x_i)|, |F(x_i) - (i-1)/n|)`
+/// over the sorted samples, rejecting at `alpha = 0.05` if `D > 1.36/sqrt(n)`.
+fn kolmogorov_smirnov_test(
+    samples: &[f64],
+    distribution: &TargetDistribution,
+) -> KolmogorovSmirnovResult {
+    let n = samples.len();
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut statistic = 0.0_f64;
+    for (i, &x) in sorted.iter().enumerate() {
+        let f_x = distribution.cdf(x);
+        let upper_gap = ((i + 1) as f64 / n as f64 - f_x).abs();
+        let lower_gap = (f_x - i as f64 / n as f64).abs();
+        statistic = statistic.max(upper_gap).max(lower_gap);
+    }
+
+    let critical_value = 1.36 / (n as f64).sqrt();
+
+    KolmogorovSmirnovResult {
+        statistic,
+        critical_value,
+        p_value: kolmogorov_p_value(statistic, n),
+        passed: statistic <= critical_value,
+    }
+}
+
+/// Asymptotic Kolmogorov distribution p-value for statistic `d` over `n`
+/// samples, via the standard series `Q(lambda) = 2 * sum_k (-1)^(k-1) exp(-2k^2 lambda^2)`.
+fn kolmogorov_p_value(d: f64, n: usize) -> f64 {
+    let nf = n as f64;
+    let lambda = (nf.sqrt() + 0.12 + 0.11 / nf.sqrt()) * d;
+    let mut sum = 0.0;
+    for k in 1..=100 {
+        let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+        let term = sign * (-2.0 * (k as f64).powi(2) * lambda * lambda).exp();
+        sum += term;
+        if term.abs() < 1e-12 {
+            break;
+        }
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// A discrete target distribution for value-level (rather than bit-level)
+/// goodness-of-fit testing: a "dice roller" or "lottery" source can have a
+/// perfectly random bitstream but a biased value distribution (or vice
+/// versa), so this checks observed value counts against the intended
+/// distribution's probability mass function directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiscreteDistribution {
+    /// Uniform over the inclusive integer range `[min, max]`.
+    UniformRange { min: i64, max: i64 },
+    Bernoulli { p: f64 },
+    Binomial { n: u64, p: f64 },
+    Poisson { lambda: f64 },
+}
+
+/// Binomial probability mass function, via the log-gamma-based log binomial
+/// coefficient to avoid overflowing `n!` for even moderate `n`.
+fn binomial_pmf(n: u64, p: f64, k: u64) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let ln_choose =
+        log_gamma(n as f64 + 1.0) - log_gamma(k as f64 + 1.0) - log_gamma((n - k) as f64 + 1.0);
+    let ln_pmf = ln_choose + k as f64 * p.ln() + (n - k) as f64 * (1.0 - p).ln();
+    ln_pmf.exp()
+}
+
+/// Poisson probability mass function.
+fn poisson_pmf(lambda: f64, k: u64) -> f64 {
+    (-lambda + k as f64 * lambda.ln() - log_gamma(k as f64 + 1.0)).exp()
+}
+
+/// Expected probability per discrete value over the support relevant to
+/// `distribution`, given what was actually observed (`observed_max` only
+/// matters for the unbounded Poisson case, where it determines how many
+/// point bins to enumerate before folding the rest into a tail bin).
+fn discrete_pmf_bins(distribution: &DiscreteDistribution, observed_max: i64) -> Vec<(i64, f64)> {
+    match *distribution {
+        DiscreteDistribution::UniformRange { min, max } => {
+            let support = (max - min + 1).max(1) as f64;
+            (min..=max).map(|k| (k, 1.0 / support)).collect()
+        }
+        DiscreteDistribution::Bernoulli { p } => vec![(0, 1.0 - p), (1, p)],
+        DiscreteDistribution::Binomial { n, p } => (0..=n as i64)
+            .map(|k| (k, binomial_pmf(n, p, k as u64)))
+            .collect(),
+        DiscreteDistribution::Poisson { lambda } => {
+            let max_k = observed_max.max(0);
+            let mut bins: Vec<(i64, f64)> =
+                (0..=max_k).map(|k| (k, poisson_pmf(lambda, k as u64))).collect();
+            let covered: f64 = bins.iter().map(|&(_, p)| p).sum();
+            bins.push((max_k + 1, (1.0 - covered).max(0.0)));
+            bins
+        }
+    }
+}
+
+/// Merge consecutive bins (in value order) so that every bin's expected
+/// count is at least 5 — the standard rule of thumb for chi-square
+/// goodness-of-fit to stay well-approximated by the chi-square distribution.
+/// Any leftover bin below the threshold at the end is folded into its
+/// predecessor.
+fn merge_small_expected_bins(bins: Vec<(f64, usize)>) -> Vec<(f64, usize)> {
+    const MIN_EXPECTED: f64 = 5.0;
+    let mut merged = Vec::new();
+    let mut acc_expected = 0.0;
+    let mut acc_observed = 0usize;
+
+    for (expected, observed) in bins {
+        acc_expected += expected;
+        acc_observed += observed;
+        if acc_expected >= MIN_EXPECTED {
+            merged.push((acc_expected, acc_observed));
+            acc_expected = 0.0;
+            acc_observed = 0;
+        }
+    }
+
+    if acc_expected > 0.0 {
+        match merged.last_mut() {
+            Some(last) => {
+                last.0 += acc_expected;
+                last.1 += acc_observed;
+            }
+            None => merged.push((acc_expected, acc_observed)),
+        }
+    }
+
+    merged
+}
+
+/// Chi-square goodness-of-fit test of `samples` (raw integer values, e.g.
+/// dice rolls or lottery draws) against a declared discrete `distribution`,
+/// via direct per-value binning rather than the probability-integral
+/// transform used by the continuous `fit()` above (a discrete pmf doesn't
+/// have an invertible CDF to transform through).
+pub fn fit_discrete(
+    samples: &[i64],
+    distribution: &DiscreteDistribution,
+) -> Result<StatisticalTestResult, String> {
+    let n = samples.len();
+    if n < 5 {
+        return Err("Discrete distribution fit testing requires at least 5 samples".to_string());
+    }
+
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+    for &value in samples {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    let observed_max = *samples.iter().max().unwrap();
+
+    let pmf_bins = discrete_pmf_bins(distribution, observed_max);
+    let raw_bins: Vec<(f64, usize)> = pmf_bins
+        .iter()
+        .map(|&(value, probability)| {
+            let observed = if let DiscreteDistribution::Poisson { .. } = distribution {
+                // The last bin is the "observed_max + 1 or more" tail bin,
+                // which never has any real observations by construction.
+                if value > observed_max {
+                    0
+                } else {
+                    *counts.get(&value).unwrap_or(&0)
+                }
+            } else {
+                *counts.get(&value).unwrap_or(&0)
+            };
+            (probability * n as f64, observed)
+        })
+        .collect();
+
+    let bins = merge_small_expected_bins(raw_bins);
+    let bin_count = bins.len();
+
+    let chi_square: f64 = bins
+        .iter()
+        .map(|&(expected, observed)| {
+            let diff = observed as f64 - expected;
+            diff * diff / expected
+        })
+        .sum();
+
+    let degrees_of_freedom = bin_count.saturating_sub(1);
+    let p_value = if degrees_of_freedom > 0 {
+        igamc(degrees_of_freedom as f64 / 2.0, chi_square / 2.0)
+    } else {
+        1.0
+    };
+    let passed = p_value >= 0.01;
+
+    Ok(StatisticalTestResult {
+        test_name: "Discrete Goodness-of-Fit Test".to_string(),
+        passed,
+        statistic: chi_square,
+        p_value: Some(p_value),
+        description: format!(
+            "{} samples, {} bins (after merging for expected count >= 5), df={}, chi-square={:.3}",
+            n, bin_count, degrees_of_freedom, chi_square
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Evenly-spaced samples over `[0, n)`, which the probability integral
+    /// transform maps to an almost perfectly uniform `[0,1)` regardless of
+    /// binning, giving a deterministic stand-in for "well-fit" data.
+    fn evenly_spaced_samples(n: usize) -> Vec<f64> {
+        (0..n).map(|i| i as f64 + 0.5).collect()
+    }
+
+    #[test]
+    fn test_fit_requires_minimum_samples() {
+        let result = fit(&[1.0, 2.0], &TargetDistribution::Uniform { a: 0.0, b: 10.0 });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_evenly_spread_samples_fit_uniform_distribution() {
+        let samples = evenly_spaced_samples(500);
+        let result =
+            fit(&samples, &TargetDistribution::Uniform { a: 0.0, b: 500.0 }).unwrap();
+        assert!(result.chi_square.passed, "chi-square: {:?}", result.chi_square);
+        assert!(
+            result.kolmogorov_smirnov.passed,
+            "ks: {:?}",
+            result.kolmogorov_smirnov
+        );
+    }
+
+    #[test]
+    fn test_evenly_spread_samples_fail_narrow_normal_distribution() {
+        let samples = evenly_spaced_samples(500);
+        let result = fit(
+            &samples,
+            &TargetDistribution::Normal {
+                mean: 250.0,
+                std_dev: 1.0,
+            },
+        )
+        .unwrap();
+        assert!(!result.chi_square.passed);
+        assert!(!result.kolmogorov_smirnov.passed);
+    }
+
+    #[test]
+    fn test_exponential_cdf_matches_known_values() {
+        let dist = TargetDistribution::Exponential { lambda: 1.0 };
+        assert_eq!(dist.cdf(0.0), 0.0);
+        assert!((dist.cdf(1.0) - (1.0 - std::f64::consts::E.recip())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_chi_square_degrees_of_freedom_accounts_for_params() {
+        let samples = evenly_spaced_samples(200);
+        let result = fit(&samples, &TargetDistribution::Exponential { lambda: 0.01 }).unwrap();
+        // 10 bins - 1 - 1 param = 8 degrees of freedom.
+        assert_eq!(result.chi_square.degrees_of_freedom, 8);
+    }
+
+    /// `n` evenly-split copies of each value in `values`, a deterministic
+    /// stand-in for "perfectly matches the target distribution's pmf" (for
+    /// distributions where that pmf happens to be uniform over `values`).
+    fn balanced_discrete_samples(values: &[i64], n: usize) -> Vec<i64> {
+        values.iter().cycle().take(values.len() * n).copied().collect()
+    }
+
+    #[test]
+    fn test_fit_discrete_requires_minimum_samples() {
+        let result = fit_discrete(&[1, 2], &DiscreteDistribution::Bernoulli { p: 0.5 });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_balanced_die_rolls_fit_uniform_range() {
+        let samples = balanced_discrete_samples(&[0, 1, 2, 3, 4, 5], 50);
+        let result = fit_discrete(
+            &samples,
+            &DiscreteDistribution::UniformRange { min: 0, max: 5 },
+        )
+        .unwrap();
+        assert!(result.passed, "{:?}", result);
+    }
+
+    #[test]
+    fn test_biased_die_rolls_fail_uniform_range() {
+        let mut samples = balanced_discrete_samples(&[0, 1, 2, 3, 4, 5], 50);
+        // Flip almost everything to a single face - a heavily loaded die.
+        for s in samples.iter_mut().take(290) {
+            *s = 0;
+        }
+        let result = fit_discrete(
+            &samples,
+            &DiscreteDistribution::UniformRange { min: 0, max: 5 },
+        )
+        .unwrap();
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn test_balanced_coin_flips_fit_bernoulli() {
+        let samples = balanced_discrete_samples(&[0, 1], 200);
+        let result = fit_discrete(&samples, &DiscreteDistribution::Bernoulli { p: 0.5 }).unwrap();
+        assert!(result.passed, "{:?}", result);
+    }
+
+    #[test]
+    fn test_biased_coin_flips_fail_bernoulli() {
+        let mut samples = vec![1i64; 400];
+        samples.extend(vec![0i64; 5]);
+        let result = fit_discrete(&samples, &DiscreteDistribution::Bernoulli { p: 0.5 }).unwrap();
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn test_binomial_pmf_sums_to_one() {
+        let n = 10;
+        let p = 0.3;
+        let total: f64 = (0..=n).map(|k| binomial_pmf(n, p, k)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_poisson_pmf_known_value() {
+        // Poisson(lambda=1) at k=0 is e^-1.
+        let pmf = poisson_pmf(1.0, 0);
+        assert!((pmf - std::f64::consts::E.recip()).abs() < 1e-9);
+    }
+}
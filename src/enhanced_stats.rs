@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 /// These tests work well with limited data where NIST tests cannot run
 use std::collections::HashMap;
 
+use crate::special_functions::{erfc, igamc};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatisticalTestResult {
     pub test_name: String,
@@ -31,6 +33,8 @@ pub fn run_enhanced_tests_structured(bits: &[u8]) -> EnhancedTestResults {
         poker_test(bits),
         autocorrelation_test(bits),
         pattern_distribution_test(bits),
+        spectral_test(bits),
+        approximate_entropy_test(bits),
     ];
 
     // Calculate overall statistics
@@ -113,12 +117,12 @@ pub fn frequency_test(bits: &[u8]) -> StatisticalTestResult {
     let ones = bits.iter().filter(|&&b| b == 1).count() as f64;
     let zeros = (bits.len() - ones as usize) as f64;
 
-    // Calculate test statistic: |ones - zeros| / sqrt(n)
+    // Test statistic: s_obs = |ones - zeros| / sqrt(n)
     let statistic = ((ones - zeros).abs()) / n.sqrt();
 
-    // Approximate p-value using normal distribution
-    // For a good sequence, statistic should be < 2.0
-    let passed = statistic < 2.0;
+    // NIST SP 800-22 monobit p-value: p = erfc(s_obs / sqrt(2))
+    let p_value = erfc(statistic / std::f64::consts::SQRT_2);
+    let passed = p_value >= 0.01;
     let description = format!(
         "Ones: {:.0}, Zeros: {:.0}, Ratio: {:.3} (expect ~0.500)",
         ones,
@@ -130,7 +134,7 @@ pub fn frequency_test(bits: &[u8]) -> StatisticalTestResult {
         test_name: "Frequency Test".to_string(),
         passed,
         statistic,
-        p_value: None,
+        p_value: Some(p_value),
         description,
     }
 }
@@ -150,7 +154,7 @@ pub fn runs_test(bits: &[u8]) -> StatisticalTestResult {
 
     let n = bits.len() as f64;
     let ones = bits.iter().filter(|&&b| b == 1).count() as f64;
-    let prop = ones / n;
+    let pi = ones / n;
 
     // Count runs (sequences of consecutive identical bits)
     let mut runs = 1;
@@ -159,30 +163,39 @@ pub fn runs_test(bits: &[u8]) -> StatisticalTestResult {
             runs += 1;
         }
     }
+    let v_obs = runs as f64;
+
+    // NIST SP 800-22 runs test. If pi isn't close enough to 0.5 (i.e. the
+    // monobit test would already have failed), the runs test isn't
+    // meaningful and its p-value is defined to be 0.
+    if (pi - 0.5).abs() >= 2.0 / n.sqrt() {
+        let description = format!(
+            "Observed runs: {}, proportion of ones: {:.3} (too far from 0.5 for the runs test to apply)",
+            runs, pi
+        );
+        return StatisticalTestResult {
+            test_name: "Runs Test".to_string(),
+            passed: false,
+            statistic: v_obs,
+            p_value: Some(0.0),
+            description,
+        };
+    }
 
-    // Expected runs for random sequence
-    let expected_runs = 2.0 * n * prop * (1.0 - prop) + 1.0;
-    let variance = 2.0 * n * prop * (1.0 - prop) * (2.0 * n * prop * (1.0 - prop) - n);
-    let std_dev = variance.sqrt();
-
-    // Calculate test statistic
-    let statistic = if std_dev > 0.0 {
-        ((runs as f64) - expected_runs).abs() / std_dev
-    } else {
-        0.0
-    };
-
-    let passed = statistic < 2.0;
+    let statistic =
+        (v_obs - 2.0 * n * pi * (1.0 - pi)).abs() / (2.0 * (2.0 * n).sqrt() * pi * (1.0 - pi));
+    let p_value = erfc(statistic);
+    let passed = p_value >= 0.01;
     let description = format!(
-        "Observed runs: {}, Expected: {:.1}, Statistic: {:.3}",
-        runs, expected_runs, statistic
+        "Observed runs: {}, proportion of ones: {:.3}, statistic: {:.3}",
+        runs, pi, statistic
     );
 
     StatisticalTestResult {
         test_name: "Runs Test".to_string(),
         passed,
         statistic,
-        p_value: None,
+        p_value: Some(p_value),
         description,
     }
 }
@@ -275,8 +288,9 @@ fn poker_test(bits: &[u8]) -> StatisticalTestResult {
         chi_square += (diff * diff) / expected_count;
     }
 
-    // For 15 degrees of freedom, chi-square should be < 25 (roughly)
-    let passed = chi_square < 25.0 && num_blocks >= 4;
+    // 16 possible 4-bit patterns, so df = 16 - 1 = 15.
+    let p_value = igamc(15.0 / 2.0, chi_square / 2.0);
+    let passed = p_value >= 0.01 && num_blocks >= 4;
     let description = format!(
         "Patterns found: {}/16, Chi-square: {:.2}, {} blocks analyzed",
         pattern_counts.len(),
@@ -288,7 +302,7 @@ fn poker_test(bits: &[u8]) -> StatisticalTestResult {
         test_name: "Poker Test (Pattern Distribution)".to_string(),
         passed,
         statistic: chi_square,
-        p_value: None,
+        p_value: Some(p_value),
         description,
     }
 }
@@ -393,6 +407,122 @@ fn pattern_distribution_test(bits: &[u8]) -> StatisticalTestResult {
     }
 }
 
+/// Discrete Fourier Transform (Spectral) Test
+/// Maps bits to +-1 and looks for periodic features via the DFT: too few
+/// frequency components below the 95%-confidence peak threshold indicates
+/// non-random periodicity.
+fn spectral_test(bits: &[u8]) -> StatisticalTestResult {
+    let n = bits.len();
+    if n < 2 {
+        return StatisticalTestResult {
+            test_name: "Discrete Fourier Transform (Spectral) Test".to_string(),
+            passed: false,
+            statistic: 0.0,
+            p_value: None,
+            description: "Insufficient data (need at least 2 bits)".to_string(),
+        };
+    }
+
+    let x: Vec<f64> = bits
+        .iter()
+        .map(|&b| if b == 1 { 1.0 } else { -1.0 })
+        .collect();
+
+    // A naive O(n^2) DFT is fine at the scale this module targets.
+    let n_f = n as f64;
+    let half = n / 2;
+    let magnitudes: Vec<f64> = (0..half)
+        .map(|k| {
+            let (mut re, mut im) = (0.0, 0.0);
+            for (t, &xt) in x.iter().enumerate() {
+                let angle = -2.0 * std::f64::consts::PI * (k as f64) * (t as f64) / n_f;
+                re += xt * angle.cos();
+                im += xt * angle.sin();
+            }
+            (re * re + im * im).sqrt()
+        })
+        .collect();
+
+    let threshold = (1.0_f64 / 0.05).ln().sqrt() * n_f.sqrt();
+    let expected_peaks = 0.95 * n_f / 2.0;
+    let observed_peaks = magnitudes.iter().filter(|&&m| m < threshold).count() as f64;
+
+    let statistic = (observed_peaks - expected_peaks) / (n_f * 0.95 * 0.05 / 4.0).sqrt();
+    let p_value = erfc(statistic.abs() / std::f64::consts::SQRT_2);
+    let passed = p_value >= 0.01;
+    let description = format!(
+        "Peaks below threshold: {:.0}/{} (expected {:.1}), d = {:.4}",
+        observed_peaks, half, expected_peaks, statistic
+    );
+
+    StatisticalTestResult {
+        test_name: "Discrete Fourier Transform (Spectral) Test".to_string(),
+        passed,
+        statistic,
+        p_value: Some(p_value),
+        description,
+    }
+}
+
+/// Approximate Entropy Test (m = 2)
+/// Compares the frequency of overlapping m-bit and (m+1)-bit patterns
+/// against what a random sequence would produce.
+fn approximate_entropy_test(bits: &[u8]) -> StatisticalTestResult {
+    const M: usize = 2;
+
+    let n = bits.len();
+    if n < 8 {
+        return StatisticalTestResult {
+            test_name: "Approximate Entropy Test".to_string(),
+            passed: false,
+            statistic: 0.0,
+            p_value: None,
+            description: "Insufficient data (need at least 8 bits)".to_string(),
+        };
+    }
+
+    let phi_m = phi_statistic(bits, M);
+    let phi_m1 = phi_statistic(bits, M + 1);
+    let apen = phi_m - phi_m1;
+    let chi_square = 2.0 * n as f64 * (std::f64::consts::LN_2 - apen);
+    let p_value = igamc(2.0_f64.powi(M as i32 - 1), chi_square / 2.0);
+    let passed = p_value >= 0.01;
+    let description = format!("ApEn({}): {:.4}, Chi-square: {:.4}", M, apen, chi_square);
+
+    StatisticalTestResult {
+        test_name: "Approximate Entropy Test".to_string(),
+        passed,
+        statistic: chi_square,
+        p_value: Some(p_value),
+        description,
+    }
+}
+
+/// phi(m) = sum over observed overlapping (wrapping) m-bit patterns of
+/// Ci * ln(Ci), where Ci is each pattern's observed proportion.
+fn phi_statistic(bits: &[u8], m: usize) -> f64 {
+    let n = bits.len();
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+
+    for i in 0..n {
+        let mut pattern: u64 = 0;
+        for j in 0..m {
+            let bit = bits[(i + j) % n] as u64;
+            pattern = (pattern << 1) | bit;
+        }
+        *counts.entry(pattern).or_insert(0) += 1;
+    }
+
+    let n_f = n as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let proportion = count as f64 / n_f;
+            proportion * proportion.ln()
+        })
+        .sum()
+}
+
 fn find_max_consecutive_same(bits: &[u8]) -> usize {
     if bits.is_empty() {
         return 0;
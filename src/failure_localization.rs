@@ -0,0 +1,195 @@
+//! Delta-debugging based failure localization.
+//!
+//! Borrows the test-case-minimization idea from property-based testing
+//! engines: given a bit sequence and a statistical test that fails on it,
+//! shrink the sequence to the smallest contiguous sub-window that still
+//! fails the same test. This turns "Pattern Distribution Test failed" into
+//! "bits 4096..4160 are the non-random region," which is far more
+//! actionable for someone debugging a hardware entropy source than a single
+//! pass/fail verdict over the whole input.
+//!
+//! This is a restricted variant of classic `ddmin`: the standard algorithm
+//! can remove any chunk (including one from the middle), which in general
+//! leaves two disjoint pieces stitched together rather than a contiguous
+//! slice. Since the whole point here is to report a `[start, end)` window
+//! into the original input, only candidate windows and prefix/suffix
+//! removals (both always contiguous) are tried.
+
+use crate::enhanced_stats::StatisticalTestResult;
+
+/// The minimal contiguous window of the original bit sequence that still
+/// fails the test, plus its `[start, end)` offsets into the original input.
+#[derive(Debug, Clone)]
+pub struct FailureLocalization {
+    pub start: usize,
+    pub end: usize,
+    pub minimal_window: Vec<u8>,
+    pub original_len: usize,
+}
+
+/// Shrink `bits` to the smallest contiguous sub-window that still fails
+/// `test` (i.e. `test(window).passed` is `false`), via delta-debugging.
+///
+/// `bits` itself must already fail `test`; if it doesn't, the whole input is
+/// returned unchanged as a degenerate "minimal" window.
+pub fn localize_failure(
+    bits: &[u8],
+    test: fn(&[u8]) -> StatisticalTestResult,
+) -> FailureLocalization {
+    let original_len = bits.len();
+
+    if original_len == 0 || test(bits).passed {
+        return FailureLocalization {
+            start: 0,
+            end: original_len,
+            minimal_window: bits.to_vec(),
+            original_len,
+        };
+    }
+
+    let mut start = 0usize;
+    let mut end = original_len;
+    let mut n = 2usize;
+
+    loop {
+        let len = end - start;
+        if len < 2 || n > len {
+            break;
+        }
+
+        let chunk_size = len.div_ceil(n);
+        let mut reduced = false;
+
+        // Try each single chunk as the candidate window.
+        for i in 0..n {
+            let chunk_start = start + i * chunk_size;
+            let chunk_end = (chunk_start + chunk_size).min(end);
+            if chunk_start >= chunk_end {
+                continue;
+            }
+            if !test(&bits[chunk_start..chunk_end]).passed {
+                start = chunk_start;
+                end = chunk_end;
+                n = 2;
+                reduced = true;
+                break;
+            }
+        }
+        if reduced {
+            continue;
+        }
+
+        // Try dropping the first chunk (keep the rest).
+        let first_chunk_end = (start + chunk_size).min(end);
+        if first_chunk_end < end && !test(&bits[first_chunk_end..end]).passed {
+            start = first_chunk_end;
+            n = 2;
+            continue;
+        }
+
+        // Try dropping the last chunk (keep the rest).
+        let last_chunk_start = (start + (n.saturating_sub(1)) * chunk_size).min(end);
+        if last_chunk_start > start && !test(&bits[start..last_chunk_start]).passed {
+            end = last_chunk_start;
+            n = 2;
+            continue;
+        }
+
+        // Neither a single chunk nor a prefix/suffix removal preserved the
+        // failure at this granularity; look more finely.
+        if n >= len {
+            break;
+        }
+        n = (n * 2).min(len);
+    }
+
+    FailureLocalization {
+        start,
+        end,
+        minimal_window: bits[start..end].to_vec(),
+        original_len,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passing_result() -> StatisticalTestResult {
+        StatisticalTestResult {
+            test_name: "synthetic".to_string(),
+            passed: true,
+            statistic: 0.0,
+            p_value: None,
+            description: String::new(),
+        }
+    }
+
+    fn failing_result() -> StatisticalTestResult {
+        StatisticalTestResult {
+            test_name: "synthetic".to_string(),
+            passed: false,
+            statistic: 0.0,
+            p_value: None,
+            description: String::new(),
+        }
+    }
+
+    /// Fails iff the window contains a run of 4 or more consecutive 1s.
+    fn has_long_run_of_ones(bits: &[u8]) -> StatisticalTestResult {
+        let has_run = bits.windows(4).any(|w| w.iter().all(|&b| b == 1));
+        if has_run {
+            failing_result()
+        } else {
+            passing_result()
+        }
+    }
+
+    #[test]
+    fn test_localize_failure_on_already_passing_input_returns_whole_input() {
+        let bits = vec![0u8; 50];
+        let result = localize_failure(&bits, has_long_run_of_ones);
+        assert_eq!(result.start, 0);
+        assert_eq!(result.end, 50);
+        assert_eq!(result.minimal_window, bits);
+    }
+
+    #[test]
+    fn test_localize_failure_on_empty_input() {
+        let bits: Vec<u8> = vec![];
+        let result = localize_failure(&bits, has_long_run_of_ones);
+        assert_eq!(result.start, 0);
+        assert_eq!(result.end, 0);
+        assert!(result.minimal_window.is_empty());
+    }
+
+    #[test]
+    fn test_localize_failure_finds_injected_bad_region() {
+        let mut bits = vec![0u8; 100];
+        for b in bits.iter_mut().skip(40).take(6) {
+            *b = 1;
+        }
+
+        let result = localize_failure(&bits, has_long_run_of_ones);
+
+        // The minimal window must still fail the test...
+        assert!(!has_long_run_of_ones(&result.minimal_window).passed);
+        // ...must be a genuine shrink from the original 100 bits...
+        assert!(result.minimal_window.len() < 100);
+        // ...and must overlap the injected region, since that's the only
+        // part of the input that can possibly fail this test.
+        assert!(result.start < 46 && result.end > 40);
+        assert_eq!(result.original_len, 100);
+    }
+
+    #[test]
+    fn test_localize_failure_offsets_match_window() {
+        let mut bits = vec![0u8; 64];
+        for b in bits.iter_mut().skip(10).take(4) {
+            *b = 1;
+        }
+
+        let result = localize_failure(&bits, has_long_run_of_ones);
+        assert_eq!(&bits[result.start..result.end], result.minimal_window.as_slice());
+    }
+}
@@ -0,0 +1,229 @@
+//! Pluggable output formatters for test results.
+//!
+//! `EnhancedTestResults` and `NistResults` already serialize via serde for
+//! API consumers, but CI pipelines generally want to ingest validator runs
+//! directly in a format their test-reporting tooling already understands,
+//! rather than screen-scraping the human-readable summary. `Formatter`
+//! gives a single trait both result types can be rendered through; the
+//! concrete formatters (JSON-lines, TAP, JUnit XML) only need to know about
+//! the common `FormattableTest` shape, not where each test result type came
+//! from.
+
+use serde::Serialize;
+
+use crate::enhanced_stats::StatisticalTestResult;
+use crate::NistTestResult;
+
+/// A single test result, reduced to the fields every formatter needs,
+/// regardless of whether it came from the small-dataset battery or the NIST
+/// tier suite.
+#[derive(Debug, Clone, Serialize)]
+pub struct FormattableTest {
+    pub name: String,
+    pub passed: bool,
+    pub p_value: Option<f64>,
+    pub description: String,
+}
+
+impl From<&StatisticalTestResult> for FormattableTest {
+    fn from(result: &StatisticalTestResult) -> Self {
+        FormattableTest {
+            name: result.test_name.clone(),
+            passed: result.passed,
+            p_value: result.p_value,
+            description: result.description.clone(),
+        }
+    }
+}
+
+impl From<&NistTestResult> for FormattableTest {
+    fn from(result: &NistTestResult) -> Self {
+        FormattableTest {
+            name: result.name.clone(),
+            passed: result.passed,
+            p_value: Some(result.p_value),
+            description: result.description.clone(),
+        }
+    }
+}
+
+/// Renders a set of test results into a specific output format.
+pub trait Formatter {
+    fn format(&self, tests: &[FormattableTest]) -> String;
+}
+
+/// One JSON object per line (streaming-friendly; no enclosing array).
+pub struct JsonLinesFormatter;
+
+impl Formatter for JsonLinesFormatter {
+    fn format(&self, tests: &[FormattableTest]) -> String {
+        tests
+            .iter()
+            .map(|t| serde_json::to_string(t).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Test Anything Protocol (TAP) version 13.
+pub struct TapFormatter;
+
+impl Formatter for TapFormatter {
+    fn format(&self, tests: &[FormattableTest]) -> String {
+        let mut output = format!("TAP version 13\n1..{}\n", tests.len());
+        for (i, test) in tests.iter().enumerate() {
+            let status = if test.passed { "ok" } else { "not ok" };
+            let p_value_comment = match test.p_value {
+                Some(p) => format!(" # p={:.6}", p),
+                None => String::new(),
+            };
+            output.push_str(&format!(
+                "{} {} - {}{}\n",
+                status,
+                i + 1,
+                test.name,
+                p_value_comment
+            ));
+        }
+        output
+    }
+}
+
+/// JUnit XML: a single `<testsuite>` with one `<testcase>` per test, and a
+/// `<failure>` child element when the test didn't pass.
+pub struct JUnitXmlFormatter;
+
+impl Formatter for JUnitXmlFormatter {
+    fn format(&self, tests: &[FormattableTest]) -> String {
+        let total = tests.len();
+        let failures = tests.iter().filter(|t| !t.passed).count();
+
+        let mut output = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <testsuite name=\"randomnumbervalidator\" tests=\"{}\" failures=\"{}\">\n",
+            total, failures
+        );
+
+        for test in tests {
+            output.push_str(&format!(
+                "  <testcase name=\"{}\">\n",
+                escape_xml(&test.name)
+            ));
+            if !test.passed {
+                let message = match test.p_value {
+                    Some(p) => format!("p-value {:.6} below significance threshold", p),
+                    None => "test failed".to_string(),
+                };
+                output.push_str(&format!(
+                    "    <failure message=\"{}\">{}</failure>\n",
+                    escape_xml(&message),
+                    escape_xml(&test.description)
+                ));
+            }
+            output.push_str("  </testcase>\n");
+        }
+
+        output.push_str("</testsuite>\n");
+        output
+    }
+}
+
+/// Escape the characters XML requires escaping in attribute values and text content.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tests() -> Vec<FormattableTest> {
+        vec![
+            FormattableTest {
+                name: "Frequency Test".to_string(),
+                passed: true,
+                p_value: Some(0.5),
+                description: "balanced".to_string(),
+            },
+            FormattableTest {
+                name: "Runs Test".to_string(),
+                passed: false,
+                p_value: Some(0.001),
+                description: "too few runs".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json_lines_one_object_per_line() {
+        let output = JsonLinesFormatter.format(&sample_tests());
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("\"name\":\"Frequency Test\""));
+        assert!(lines[1].contains("\"passed\":false"));
+    }
+
+    #[test]
+    fn test_tap_format_has_plan_and_status_lines() {
+        let output = TapFormatter.format(&sample_tests());
+        assert!(output.starts_with("TAP version 13\n1..2\n"));
+        assert!(output.contains("ok 1 - Frequency Test"));
+        assert!(output.contains("not ok 2 - Runs Test"));
+    }
+
+    #[test]
+    fn test_junit_xml_has_failure_element_only_for_failing_tests() {
+        let output = JUnitXmlFormatter.format(&sample_tests());
+        assert!(output.contains("tests=\"2\" failures=\"1\""));
+        assert!(output.contains("<testcase name=\"Frequency Test\">"));
+        assert!(output.contains("<failure message="));
+        let failure_count = output.matches("<failure").count();
+        assert_eq!(failure_count, 1);
+    }
+
+    #[test]
+    fn test_junit_xml_escapes_special_characters() {
+        let tests = vec![FormattableTest {
+            name: "A < B & \"C\"".to_string(),
+            passed: false,
+            p_value: None,
+            description: "desc".to_string(),
+        }];
+        let output = JUnitXmlFormatter.format(&tests);
+        assert!(output.contains("A &lt; B &amp; &quot;C&quot;"));
+    }
+
+    #[test]
+    fn test_formattable_test_from_statistical_test_result() {
+        let result = StatisticalTestResult {
+            test_name: "Poker Test".to_string(),
+            passed: true,
+            statistic: 1.23,
+            p_value: Some(0.9),
+            description: "desc".to_string(),
+        };
+        let formattable: FormattableTest = (&result).into();
+        assert_eq!(formattable.name, "Poker Test");
+        assert_eq!(formattable.p_value, Some(0.9));
+    }
+
+    #[test]
+    fn test_formattable_test_from_nist_test_result() {
+        let result = NistTestResult {
+            name: "Frequency".to_string(),
+            passed: false,
+            p_value: 0.002,
+            p_values: vec![0.002],
+            description: "desc".to_string(),
+            metrics: None,
+        };
+        let formattable: FormattableTest = (&result).into();
+        assert_eq!(formattable.name, "Frequency");
+        assert_eq!(formattable.p_value, Some(0.002));
+        assert!(!formattable.passed);
+    }
+}
@@ -0,0 +1,66 @@
+//! Offline GeoIP country resolution for the `queries.country` column.
+//!
+//! Wraps a `maxminddb::Reader` over a GeoLite2-Country (or compatible)
+//! `.mmdb` file so `server.rs` can resolve a request's `client_ip` to an
+//! ISO 3166-1 alpha-2 country code without any per-request network calls.
+//! Every lookup failure - a private/loopback address, an unparseable IP, a
+//! lookup miss - degrades to `None` rather than an error, the same way the
+//! rest of the server treats storage as optional: a request is still valid
+//! without a resolved country. A missing or unreadable database file makes
+//! `GeoIpDatabase::open` return `Err`, and the server then runs without GeoIP.
+
+use std::net::IpAddr;
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// Thin wrapper over `maxminddb::Reader<Vec<u8>>` so callers don't need to
+/// depend on the `maxminddb` crate directly for the one lookup they need.
+pub struct GeoIpDatabase {
+    reader: maxminddb::Reader<Vec<u8>>,
+}
+
+impl GeoIpDatabase {
+    /// Load a GeoLite2-Country `.mmdb` file from `path`. Returns `Err` (with
+    /// a message describing what went wrong) rather than panicking, so the
+    /// caller can log a warning and continue without GeoIP instead of
+    /// failing the whole server start.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let reader = maxminddb::Reader::open_readfile(path.as_ref())
+            .map_err(|e| format!("Failed to open GeoIP database '{}': {}", path.as_ref().display(), e))?;
+        Ok(GeoIpDatabase { reader })
+    }
+
+    /// Resolve `client_ip` to an ISO 3166-1 alpha-2 country code, or `None`
+    /// if the address can't be parsed, is private/loopback (never present
+    /// in a GeoIP database), or simply isn't found.
+    pub fn lookup_country(&self, client_ip: &str) -> Option<String> {
+        let ip: IpAddr = client_ip.parse().ok()?;
+        if is_private_or_loopback(&ip) {
+            debug!("Skipping GeoIP lookup for private/loopback address {}", client_ip);
+            return None;
+        }
+
+        match self.reader.lookup::<maxminddb::geoip2::Country>(ip) {
+            Ok(Some(country)) => country
+                .country
+                .and_then(|c| c.iso_code)
+                .map(|code| code.to_string()),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("GeoIP lookup failed for {}: {}", client_ip, e);
+                None
+            }
+        }
+    }
+}
+
+/// `client_ip` is extracted from `X-Forwarded-For`/`X-Real-IP` or the
+/// socket's peer address, any of which can legitimately be a private or
+/// loopback address (local development, a misconfigured proxy) - looking
+/// those up would always miss, so skip the call entirely.
+fn is_private_or_loopback(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_unspecified() || v4.is_link_local(),
+        IpAddr::V6(v6) => v6.is_loopback() || v6.is_unspecified(),
+    }
+}
@@ -0,0 +1,81 @@
+//! Durable job queue for asynchronous NIST validation runs.
+//!
+//! The normal request path (`src/bin/server.rs`) validates a number stream
+//! synchronously and, if a `ResultStore` is configured, writes one `queries`
+//! row plus one `test_results` row per NIST test. For very large streams a
+//! client may instead want to submit a batch, get a job id back
+//! immediately, and have a background worker run the validation and record
+//! results later. `enqueue` that batch here; a worker loop calls `read` to
+//! claim the oldest visible job (hiding it from other workers for `vt`
+//! seconds while it works), then `archive`s it on success or `delete`s it,
+//! mirroring a Postgres message queue's visibility-timeout model.
+//!
+//! Like `storage`, this is a trait (`JobQueue`) with one implementation per
+//! backend (`postgres`, `sqlite`) behind a matching `JobQueueConfig` /
+//! `connect`.
+
+mod postgres;
+mod sqlite;
+
+pub use postgres::PostgresJobQueue;
+pub use sqlite::SqliteJobQueue;
+
+use crate::ValidationRequest;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// A claimed or pending validation job.
+#[derive(Debug)]
+pub struct ValidationJob {
+    pub msg_id: i64,
+    pub enqueued_at: DateTime<Utc>,
+    pub vt: DateTime<Utc>,
+    pub read_ct: i32,
+    pub message: ValidationRequest,
+}
+
+/// Point-in-time throughput/backlog snapshot for operators.
+#[derive(Debug, Clone)]
+pub struct QueueMetrics {
+    pub queue_length: i64,
+    pub oldest_msg_age_secs: Option<i64>,
+    pub newest_msg_age_secs: Option<i64>,
+    pub total_processed: i64,
+    pub scraped_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait JobQueue: Send + Sync {
+    async fn run_migrations(&self) -> Result<(), String>;
+
+    /// Submit a batch for later processing, returning its assigned `msg_id`.
+    async fn enqueue(&self, message: &ValidationRequest) -> Result<i64, String>;
+
+    /// Claim the oldest visible job, hiding it from other readers for
+    /// `visibility_timeout_secs`. Returns `None` if the queue has no
+    /// visible job right now.
+    async fn read(&self, visibility_timeout_secs: i64) -> Result<Option<ValidationJob>, String>;
+
+    /// Move a claimed job to the archive table and remove it from the
+    /// active queue, recording that it was processed.
+    async fn archive(&self, job: &ValidationJob) -> Result<(), String>;
+
+    /// Drop a claimed job without archiving it (e.g. it was invalid).
+    async fn delete(&self, msg_id: i64) -> Result<(), String>;
+
+    async fn metrics(&self) -> Result<QueueMetrics, String>;
+}
+
+pub enum JobQueueConfig {
+    Postgres(String),
+    Sqlite(String),
+}
+
+pub async fn connect(config: JobQueueConfig) -> Result<Box<dyn JobQueue>, String> {
+    let queue: Box<dyn JobQueue> = match config {
+        JobQueueConfig::Postgres(url) => Box::new(PostgresJobQueue::connect(&url).await?),
+        JobQueueConfig::Sqlite(url) => Box::new(SqliteJobQueue::connect(&url).await?),
+    };
+    queue.run_migrations().await?;
+    Ok(queue)
+}
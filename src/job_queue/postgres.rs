@@ -0,0 +1,162 @@
+//! `JobQueue` implementation backed by PostgreSQL.
+//!
+//! `read` uses `FOR UPDATE SKIP LOCKED` so multiple worker processes can
+//! poll the same queue concurrently without claiming the same job twice.
+
+use super::{JobQueue, QueueMetrics, ValidationJob};
+use crate::ValidationRequest;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+
+pub struct PostgresJobQueue {
+    pool: PgPool,
+}
+
+impl PostgresJobQueue {
+    pub async fn connect(database_url: &str) -> Result<Self, String> {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .map_err(|e| format!("Failed to connect to Postgres: {}", e))?;
+        Ok(PostgresJobQueue { pool })
+    }
+}
+
+#[async_trait]
+impl JobQueue for PostgresJobQueue {
+    async fn run_migrations(&self) -> Result<(), String> {
+        sqlx::migrate!("./migrations/postgres")
+            .run(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to run Postgres migrations: {}", e))
+    }
+
+    async fn enqueue(&self, message: &ValidationRequest) -> Result<i64, String> {
+        let message_json = serde_json::to_value(message)
+            .map_err(|e| format!("Failed to serialize job message: {}", e))?;
+
+        let msg_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO validation_jobs (message)
+            VALUES ($1::jsonb)
+            RETURNING msg_id
+            "#,
+            message_json,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to enqueue validation job: {}", e))?;
+
+        Ok(msg_id)
+    }
+
+    async fn read(&self, visibility_timeout_secs: i64) -> Result<Option<ValidationJob>, String> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE validation_jobs
+            SET vt = NOW() + ($1 * INTERVAL '1 second'), read_ct = read_ct + 1
+            WHERE msg_id = (
+                SELECT msg_id FROM validation_jobs
+                WHERE vt <= NOW()
+                ORDER BY msg_id
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+            )
+            RETURNING msg_id, enqueued_at, vt, read_ct, message
+            "#,
+            visibility_timeout_secs,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to read validation job: {}", e))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let message: ValidationRequest = serde_json::from_value(row.message)
+            .map_err(|e| format!("Failed to deserialize job message: {}", e))?;
+
+        Ok(Some(ValidationJob {
+            msg_id: row.msg_id,
+            enqueued_at: row.enqueued_at,
+            vt: row.vt,
+            read_ct: row.read_ct,
+            message,
+        }))
+    }
+
+    async fn archive(&self, job: &ValidationJob) -> Result<(), String> {
+        let message_json = serde_json::to_value(&job.message)
+            .map_err(|e| format!("Failed to serialize job message: {}", e))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start archive transaction: {}", e))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO validation_jobs_archive (msg_id, enqueued_at, read_ct, message)
+            VALUES ($1, $2, $3, $4::jsonb)
+            "#,
+            job.msg_id,
+            job.enqueued_at,
+            job.read_ct,
+            message_json,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to archive validation job: {}", e))?;
+
+        sqlx::query!(
+            "DELETE FROM validation_jobs WHERE msg_id = $1::bigint",
+            job.msg_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to remove archived job from queue: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit archive transaction: {}", e))
+    }
+
+    async fn delete(&self, msg_id: i64) -> Result<(), String> {
+        sqlx::query!("DELETE FROM validation_jobs WHERE msg_id = $1::bigint", msg_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete validation job: {}", e))?;
+        Ok(())
+    }
+
+    async fn metrics(&self) -> Result<QueueMetrics, String> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM validation_jobs) AS "queue_length!",
+                (SELECT MIN(enqueued_at) FROM validation_jobs) AS oldest,
+                (SELECT MAX(enqueued_at) FROM validation_jobs) AS newest,
+                (SELECT COUNT(*) FROM validation_jobs_archive) AS "total_processed!"
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to compute queue metrics: {}", e))?;
+
+        let scraped_at = Utc::now();
+        let age_secs = |ts: Option<DateTime<Utc>>| ts.map(|ts| (scraped_at - ts).num_seconds());
+
+        Ok(QueueMetrics {
+            queue_length: row.queue_length,
+            oldest_msg_age_secs: age_secs(row.oldest),
+            newest_msg_age_secs: age_secs(row.newest),
+            total_processed: row.total_processed,
+            scraped_at,
+        })
+    }
+}
@@ -0,0 +1,248 @@
+//! `JobQueue` implementation backed by embedded SQLite.
+//!
+//! SQLite has no row-level locking to mirror Postgres's
+//! `FOR UPDATE SKIP LOCKED`, but a `:memory:`/file-backed SQLite pool here
+//! is single-writer anyway (see `storage::SqliteStore`), so a plain
+//! claim-by-subquery `UPDATE ... RETURNING` is enough to make `read` atomic.
+
+use super::{JobQueue, QueueMetrics, ValidationJob};
+use crate::ValidationRequest;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+
+pub struct SqliteJobQueue {
+    pool: SqlitePool,
+}
+
+impl SqliteJobQueue {
+    pub async fn connect(database_url: &str) -> Result<Self, String> {
+        let is_memory = database_url.contains(":memory:");
+
+        let mut options = SqliteConnectOptions::from_str(database_url)
+            .map_err(|e| format!("Invalid SQLite URL: {}", e))?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        if !is_memory {
+            options = options.journal_mode(SqliteJournalMode::Wal);
+        }
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(if is_memory { 1 } else { 5 })
+            .connect_with(options)
+            .await
+            .map_err(|e| format!("Failed to connect to SQLite: {}", e))?;
+
+        Ok(SqliteJobQueue { pool })
+    }
+}
+
+#[async_trait]
+impl JobQueue for SqliteJobQueue {
+    async fn run_migrations(&self) -> Result<(), String> {
+        sqlx::migrate!("./migrations/sqlite")
+            .run(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to run SQLite migrations: {}", e))
+    }
+
+    async fn enqueue(&self, message: &ValidationRequest) -> Result<i64, String> {
+        let message_json = serde_json::to_string(message)
+            .map_err(|e| format!("Failed to serialize job message: {}", e))?;
+
+        let msg_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO validation_jobs (message)
+            VALUES ($1)
+            RETURNING msg_id
+            "#,
+            message_json,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to enqueue validation job: {}", e))?;
+
+        Ok(msg_id)
+    }
+
+    async fn read(&self, visibility_timeout_secs: i64) -> Result<Option<ValidationJob>, String> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE validation_jobs
+            SET vt = datetime('now', '+' || $1 || ' seconds'), read_ct = read_ct + 1
+            WHERE msg_id = (
+                SELECT msg_id FROM validation_jobs
+                WHERE vt <= datetime('now')
+                ORDER BY msg_id
+                LIMIT 1
+            )
+            RETURNING
+                msg_id as "msg_id!: i64",
+                enqueued_at as "enqueued_at: DateTime<Utc>",
+                vt as "vt: DateTime<Utc>",
+                read_ct,
+                message
+            "#,
+            visibility_timeout_secs,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to read validation job: {}", e))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let message: ValidationRequest = serde_json::from_str(&row.message)
+            .map_err(|e| format!("Failed to deserialize job message: {}", e))?;
+
+        Ok(Some(ValidationJob {
+            msg_id: row.msg_id,
+            enqueued_at: row.enqueued_at,
+            vt: row.vt,
+            read_ct: row.read_ct,
+            message,
+        }))
+    }
+
+    async fn archive(&self, job: &ValidationJob) -> Result<(), String> {
+        let message_json = serde_json::to_string(&job.message)
+            .map_err(|e| format!("Failed to serialize job message: {}", e))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start archive transaction: {}", e))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO validation_jobs_archive (msg_id, enqueued_at, read_ct, message)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            job.msg_id,
+            job.enqueued_at,
+            job.read_ct,
+            message_json,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to archive validation job: {}", e))?;
+
+        sqlx::query!(
+            "DELETE FROM validation_jobs WHERE msg_id = $1",
+            job.msg_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to remove archived job from queue: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit archive transaction: {}", e))
+    }
+
+    async fn delete(&self, msg_id: i64) -> Result<(), String> {
+        sqlx::query!("DELETE FROM validation_jobs WHERE msg_id = $1", msg_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete validation job: {}", e))?;
+        Ok(())
+    }
+
+    async fn metrics(&self) -> Result<QueueMetrics, String> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM validation_jobs) AS "queue_length!: i64",
+                (SELECT MIN(enqueued_at) FROM validation_jobs) AS "oldest: DateTime<Utc>",
+                (SELECT MAX(enqueued_at) FROM validation_jobs) AS "newest: DateTime<Utc>",
+                (SELECT COUNT(*) FROM validation_jobs_archive) AS "total_processed!: i64"
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to compute queue metrics: {}", e))?;
+
+        let scraped_at = Utc::now();
+        let age_secs = |ts: Option<DateTime<Utc>>| ts.map(|ts| (scraped_at - ts).num_seconds());
+
+        Ok(QueueMetrics {
+            queue_length: row.queue_length,
+            oldest_msg_age_secs: age_secs(row.oldest),
+            newest_msg_age_secs: age_secs(row.newest),
+            total_processed: row.total_processed,
+            scraped_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BitOrder, InputFormat};
+
+    fn sample_request() -> ValidationRequest {
+        ValidationRequest {
+            numbers: "0,1,2,3".to_string(),
+            input_format: InputFormat::Numbers,
+            range_min: None,
+            range_max: None,
+            bit_width: None,
+            bit_order: BitOrder::MsbFirst,
+            debug_log: false,
+            use_whitening: false,
+            packed_fields: None,
+            bit_selection: None,
+            with_calibration: false,
+            distribution_fit: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_then_read_claims_job() {
+        let queue = SqliteJobQueue::connect("sqlite::memory:").await.unwrap();
+        queue.run_migrations().await.unwrap();
+
+        let msg_id = queue.enqueue(&sample_request()).await.unwrap();
+
+        let job = queue.read(30).await.unwrap().expect("expected a visible job");
+        assert_eq!(job.msg_id, msg_id);
+        assert_eq!(job.read_ct, 1);
+        assert_eq!(job.message.numbers, "0,1,2,3");
+
+        // The job is now hidden until its visibility timeout elapses.
+        assert!(queue.read(30).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_archive_moves_job_out_of_active_queue() {
+        let queue = SqliteJobQueue::connect("sqlite::memory:").await.unwrap();
+        queue.run_migrations().await.unwrap();
+
+        queue.enqueue(&sample_request()).await.unwrap();
+        let job = queue.read(30).await.unwrap().unwrap();
+        queue.archive(&job).await.unwrap();
+
+        let metrics = queue.metrics().await.unwrap();
+        assert_eq!(metrics.queue_length, 0);
+        assert_eq!(metrics.total_processed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_job_without_archiving() {
+        let queue = SqliteJobQueue::connect("sqlite::memory:").await.unwrap();
+        queue.run_migrations().await.unwrap();
+
+        let msg_id = queue.enqueue(&sample_request()).await.unwrap();
+        let job = queue.read(30).await.unwrap().unwrap();
+        queue.delete(job.msg_id).await.unwrap();
+
+        let metrics = queue.metrics().await.unwrap();
+        assert_eq!(metrics.queue_length, 0);
+        assert_eq!(metrics.total_processed, 0);
+        assert_eq!(msg_id, job.msg_id);
+    }
+}
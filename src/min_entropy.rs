@@ -0,0 +1,246 @@
+//! SP 800-90B style min-entropy estimation for datasets too small for the
+//! full SP 800-22 statistical test suite.
+//!
+//! A sequence below the 100-bit Tier 1 floor (see `nist_wrapper::TestTier`)
+//! can't support frequency/runs/FFT style significance testing, but it can
+//! still be scored: SP 800-90B's non-IID track estimates min-entropy
+//! (`-log2(p_max)`, the entropy of always guessing the single most likely
+//! outcome) via several independent estimators and takes the minimum, since
+//! min-entropy is only as good as its weakest witness. This module
+//! implements three of those estimators — Most Common Value, first-order
+//! Markov, and a simple LZ78-style compression estimator — over arbitrary
+//! integer-valued symbols, so callers can run it bit-granular or
+//! byte-granular.
+
+use std::collections::{HashMap, HashSet};
+
+/// 99th-percentile z-score used by the Most Common Value estimator's upper
+/// confidence bound (SP 800-90B uses 2.576, the 99% one-sided bound).
+const Z_99: f64 = 2.576;
+
+/// Path length used by the first-order Markov estimator (SP 800-90B caps
+/// this at 128, beyond which the bound stops tightening meaningfully).
+const MARKOV_MAX_PATH_LENGTH: usize = 128;
+
+/// Per-estimator and combined min-entropy results, in bits per symbol.
+#[derive(Debug, Clone)]
+pub struct MinEntropyResult {
+    pub symbol_count: usize,
+    pub most_common_value_entropy: f64,
+    pub markov_entropy: f64,
+    pub compression_entropy: f64,
+    /// The minimum of the three estimators above — the reported min-entropy.
+    pub min_entropy_bits_per_symbol: f64,
+}
+
+/// Runs the SP 800-90B non-IID min-entropy estimators over a symbol stream.
+pub struct MinEntropyEstimator;
+
+impl MinEntropyEstimator {
+    pub fn new() -> Self {
+        MinEntropyEstimator
+    }
+
+    /// Estimate min-entropy per symbol over bit-granular symbols (each
+    /// element of `bits` is 0 or 1).
+    pub fn estimate_bits(&self, bits: &[u8]) -> Result<MinEntropyResult, String> {
+        self.estimate(&bits.iter().map(|&b| b as u64).collect::<Vec<_>>())
+    }
+
+    /// Estimate min-entropy per symbol over byte-granular symbols (packed
+    /// bytes, each treated as a single 0-255 symbol).
+    pub fn estimate_bytes(&self, bytes: &[u8]) -> Result<MinEntropyResult, String> {
+        self.estimate(&bytes.iter().map(|&b| b as u64).collect::<Vec<_>>())
+    }
+
+    /// Estimate min-entropy per symbol over an arbitrary integer-valued
+    /// symbol stream, taking the minimum over all estimators.
+    pub fn estimate(&self, symbols: &[u64]) -> Result<MinEntropyResult, String> {
+        if symbols.len() < 2 {
+            return Err("Min-entropy estimation requires at least 2 symbols".to_string());
+        }
+
+        let most_common_value_entropy = Self::most_common_value_entropy(symbols);
+        let markov_entropy = Self::markov_entropy(symbols);
+        let compression_entropy = Self::compression_entropy(symbols);
+
+        let min_entropy_bits_per_symbol = most_common_value_entropy
+            .min(markov_entropy)
+            .min(compression_entropy);
+
+        Ok(MinEntropyResult {
+            symbol_count: symbols.len(),
+            most_common_value_entropy,
+            markov_entropy,
+            compression_entropy,
+            min_entropy_bits_per_symbol,
+        })
+    }
+
+    /// Most Common Value estimator: `p_hat = max_count / L`, upper bound
+    /// `p_u = p_hat + 2.576 * sqrt(p_hat(1-p_hat)/(L-1))`, entropy `= -log2(p_u)`.
+    fn most_common_value_entropy(symbols: &[u64]) -> f64 {
+        let l = symbols.len() as f64;
+        let mut counts: HashMap<u64, usize> = HashMap::new();
+        for &s in symbols {
+            *counts.entry(s).or_insert(0) += 1;
+        }
+        let max_count = *counts.values().max().unwrap_or(&0) as f64;
+        let p_hat = max_count / l;
+        let p_u = (p_hat + Z_99 * (p_hat * (1.0 - p_hat) / (l - 1.0)).sqrt()).min(1.0);
+        -p_u.max(f64::MIN_POSITIVE).log2()
+    }
+
+    /// First-order Markov estimator: build the transition-count matrix over
+    /// the symbol alphabet, find the highest-probability length-`k` path
+    /// probability `p_max` via dynamic programming, and estimate
+    /// `-log2(p_max) / k`.
+    fn markov_entropy(symbols: &[u64]) -> f64 {
+        let k = MARKOV_MAX_PATH_LENGTH.min(symbols.len());
+
+        let mut symbol_counts: HashMap<u64, usize> = HashMap::new();
+        for &s in symbols {
+            *symbol_counts.entry(s).or_insert(0) += 1;
+        }
+
+        let mut state_counts: HashMap<u64, usize> = HashMap::new();
+        let mut transition_counts: HashMap<(u64, u64), usize> = HashMap::new();
+        for window in symbols.windows(2) {
+            *state_counts.entry(window[0]).or_insert(0) += 1;
+            *transition_counts.entry((window[0], window[1])).or_insert(0) += 1;
+        }
+
+        let total = symbols.len() as f64;
+        // best[state] = highest probability of any path of the current
+        // length ending at `state`.
+        let mut best: HashMap<u64, f64> = symbol_counts
+            .iter()
+            .map(|(&s, &c)| (s, c as f64 / total))
+            .collect();
+
+        for _ in 1..k {
+            let mut next_best: HashMap<u64, f64> = HashMap::new();
+            for (&(from, to), &count) in &transition_counts {
+                let from_prob = match best.get(&from) {
+                    Some(p) => *p,
+                    None => continue,
+                };
+                let from_count = *state_counts.get(&from).unwrap_or(&0) as f64;
+                if from_count == 0.0 {
+                    continue;
+                }
+                let candidate = from_prob * (count as f64 / from_count);
+                let entry = next_best.entry(to).or_insert(0.0);
+                if candidate > *entry {
+                    *entry = candidate;
+                }
+            }
+            if next_best.is_empty() {
+                break;
+            }
+            best = next_best;
+        }
+
+        let p_max = best
+            .values()
+            .cloned()
+            .fold(0.0_f64, f64::max)
+            .min(1.0)
+            .max(f64::MIN_POSITIVE);
+        (-p_max.log2() / k as f64).max(0.0)
+    }
+
+    /// Compression estimator: run a simple LZ78-style dictionary pass and
+    /// estimate bits-per-symbol from how few phrases were needed to cover
+    /// the stream (a generator with low entropy repeats itself, so a
+    /// dictionary coder needs far fewer phrases than symbols).
+    fn compression_entropy(symbols: &[u64]) -> f64 {
+        let mut dictionary: HashSet<Vec<u64>> = HashSet::new();
+        let mut phrase_count = 0usize;
+        let mut current: Vec<u64> = Vec::new();
+
+        for &sym in symbols {
+            current.push(sym);
+            if !dictionary.contains(&current) {
+                dictionary.insert(current.clone());
+                phrase_count += 1;
+                current.clear();
+            }
+        }
+        if !current.is_empty() {
+            phrase_count += 1;
+        }
+
+        // Each phrase costs roughly log2(phrase_count) bits to reference a
+        // dictionary entry; spread that cost over the original symbols.
+        let bits_per_phrase = (phrase_count as f64).log2().max(1.0);
+        let total_bits = phrase_count as f64 * bits_per_phrase;
+        (total_bits / symbols.len() as f64).max(0.0)
+    }
+}
+
+impl Default for MinEntropyEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_requires_at_least_two_symbols() {
+        let estimator = MinEntropyEstimator::new();
+        assert!(estimator.estimate(&[1]).is_err());
+        assert!(estimator.estimate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_constant_symbol_stream_has_near_zero_entropy() {
+        let estimator = MinEntropyEstimator::new();
+        let symbols = vec![0u64; 50];
+        let result = estimator.estimate(&symbols).unwrap();
+        assert!(result.min_entropy_bits_per_symbol < 0.2);
+    }
+
+    #[test]
+    fn test_alternating_bits_have_low_markov_entropy() {
+        // Perfectly predictable alternation should have near-zero entropy
+        // under the Markov estimator even though MCV sees a 50/50 split.
+        let estimator = MinEntropyEstimator::new();
+        let symbols: Vec<u64> = (0..100).map(|i| (i % 2) as u64).collect();
+        let result = estimator.estimate(&symbols).unwrap();
+        assert!(result.markov_entropy < 0.2);
+        assert!(result.min_entropy_bits_per_symbol <= result.markov_entropy);
+    }
+
+    #[test]
+    fn test_estimate_bits_and_bytes_granularity() {
+        let estimator = MinEntropyEstimator::new();
+        let bits: Vec<u8> = (0..64).map(|i| (i % 3 == 0) as u8).collect();
+        let bytes: Vec<u8> = (0..64).map(|i| (i * 37 % 256) as u8).collect();
+
+        let bit_result = estimator.estimate_bits(&bits).unwrap();
+        let byte_result = estimator.estimate_bytes(&bytes).unwrap();
+
+        assert_eq!(bit_result.symbol_count, 64);
+        assert_eq!(byte_result.symbol_count, 64);
+        // Bit-granular entropy is capped at 1 bit/symbol; byte-granular can
+        // range up to 8.
+        assert!(bit_result.min_entropy_bits_per_symbol <= 1.0);
+        assert!(byte_result.min_entropy_bits_per_symbol <= 8.0);
+    }
+
+    #[test]
+    fn test_varied_data_has_higher_entropy_than_constant_data() {
+        let estimator = MinEntropyEstimator::new();
+        let constant = vec![7u64; 200];
+        let varied: Vec<u64> = (0..200).map(|i| (i * 31 + 11) % 256).collect();
+
+        let constant_result = estimator.estimate(&constant).unwrap();
+        let varied_result = estimator.estimate(&varied).unwrap();
+
+        assert!(varied_result.min_entropy_bits_per_symbol > constant_result.min_entropy_bits_per_symbol);
+    }
+}
@@ -0,0 +1,194 @@
+//! Second-level NIST SP 800-22 assessment.
+//!
+//! A single p-value only answers "does this one run look random?". The
+//! SP 800-22 publication's guidance on interpreting results asks two
+//! broader questions across many equal-length subsequences of the same
+//! source: do enough of them pass (the *proportion of sequences passing*),
+//! and are the p-values themselves uniformly distributed (the
+//! *uniformity of p-values*)? This module implements both checks and the
+//! subsequence partitioning that feeds them.
+
+use std::collections::HashMap;
+
+use nistrs::prelude::*;
+
+use crate::nist_tests;
+use crate::special_functions::igamc;
+
+/// Significance level used for both the per-test pass/fail cutoff and the
+/// proportion-of-sequences-passing bound.
+const ALPHA: f64 = 0.01;
+
+/// Minimum subsequence count for the uniformity-of-p-values check to be
+/// statistically meaningful (NIST SP 800-22 recommends `m >= 55`).
+pub const MIN_SEQUENCES_FOR_UNIFORMITY: usize = 55;
+
+/// Minimum subsequence count for the proportion-of-sequences-passing check
+/// to be worth reporting at all.
+pub const MIN_SEQUENCES_FOR_PROPORTION: usize = 2;
+
+/// Result of the second-level analysis for a single test across `m`
+/// subsequences.
+#[derive(Debug, Clone)]
+pub struct SecondLevelResult {
+    /// Number of subsequences the test was run on (`m`).
+    pub sequence_count: usize,
+    /// Fraction of subsequences with `p >= 0.01`.
+    pub proportion_passing: f64,
+    /// Acceptable `(low, high)` range for `proportion_passing` at `m` sequences.
+    pub proportion_range: (f64, f64),
+    /// Whether `proportion_passing` falls within `proportion_range`.
+    pub proportion_ok: bool,
+    /// `chi^2` statistic over the 10-bin p-value histogram, if `m` was large
+    /// enough to compute it (see `MIN_SEQUENCES_FOR_UNIFORMITY`).
+    pub uniformity_chi_square: Option<f64>,
+    /// `P_T = igamc(9/2, chi^2/2)`; p-values are declared non-uniform below 0.0001.
+    pub uniformity_p_value: Option<f64>,
+    pub uniformity_ok: Option<bool>,
+}
+
+/// Acceptable proportion-of-sequences-passing range at significance `ALPHA`
+/// for `m` subsequences: `(1 - alpha) +/- 3 * sqrt(alpha(1-alpha)/m)`.
+pub fn proportion_passing_range(m: usize) -> (f64, f64) {
+    let center = 1.0 - ALPHA;
+    let spread = 3.0 * (ALPHA * (1.0 - ALPHA) / m as f64).sqrt();
+    (center - spread, center + spread)
+}
+
+/// Assess a set of p-values gathered from `m` subsequences of one test.
+pub fn assess_p_values(p_values: &[f64]) -> SecondLevelResult {
+    let m = p_values.len();
+    let passing = p_values.iter().filter(|&&p| p >= ALPHA).count();
+    let proportion_passing = passing as f64 / m as f64;
+    let proportion_range = proportion_passing_range(m);
+    let proportion_ok =
+        proportion_passing >= proportion_range.0 && proportion_passing <= proportion_range.1;
+
+    let (uniformity_chi_square, uniformity_p_value, uniformity_ok) =
+        if m >= MIN_SEQUENCES_FOR_UNIFORMITY {
+            let mut bins = [0usize; 10];
+            for &p in p_values {
+                let idx = ((p * 10.0).floor() as usize).min(9);
+                bins[idx] += 1;
+            }
+            let expected = m as f64 / 10.0;
+            let chi_square: f64 = bins
+                .iter()
+                .map(|&f| {
+                    let diff = f as f64 - expected;
+                    diff * diff / expected
+                })
+                .sum();
+            let p_t = igamc(9.0 / 2.0, chi_square / 2.0);
+            (Some(chi_square), Some(p_t), Some(p_t >= 0.0001))
+        } else {
+            (None, None, None)
+        };
+
+    SecondLevelResult {
+        sequence_count: m,
+        proportion_passing,
+        proportion_range,
+        proportion_ok,
+        uniformity_chi_square,
+        uniformity_p_value,
+        uniformity_ok,
+    }
+}
+
+/// Run every tier-applicable test on each of `m = floor(bits.len() / n)`
+/// equal-length `n`-bit subsequences, returning the per-test p-value series
+/// keyed by test name. Multi-result tests (e.g. `CumulativeSums-Forward`,
+/// `Serial-1`) collect one series per sub-result, in the same order
+/// `(test_def.execute)` returns them, mirroring how `parse_test_results`
+/// numbers them.
+pub fn collect_subsequence_p_values(
+    bits: &[u8],
+    n: usize,
+    tier_level: u8,
+    pack_bits_to_bytes: impl Fn(&[u8]) -> Vec<u8>,
+) -> HashMap<String, Vec<Vec<f64>>> {
+    let mut series: HashMap<String, Vec<Vec<f64>>> = HashMap::new();
+    if n == 0 {
+        return series;
+    }
+    let m = bits.len() / n;
+
+    for chunk_index in 0..m {
+        let chunk = &bits[chunk_index * n..(chunk_index + 1) * n];
+        let packed = pack_bits_to_bytes(chunk);
+        let data = BitsData::from_binary(packed);
+
+        for test_def in nist_tests::get_all_tests() {
+            if !test_def.should_run(tier_level, n) {
+                continue;
+            }
+            let results = (test_def.execute)(&data);
+            let entry = series.entry(test_def.name.to_string()).or_default();
+            for (i, (_, p_value)) in results.iter().enumerate() {
+                if entry.len() <= i {
+                    entry.resize(i + 1, Vec::new());
+                }
+                entry[i].push(*p_value);
+            }
+        }
+    }
+
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proportion_passing_range_shrinks_with_more_sequences() {
+        let (low_small, high_small) = proportion_passing_range(10);
+        let (low_large, high_large) = proportion_passing_range(1000);
+        assert!(high_small - low_small > high_large - low_large);
+    }
+
+    #[test]
+    fn test_assess_p_values_all_passing_is_ok() {
+        let p_values = vec![0.5; 100];
+        let result = assess_p_values(&p_values);
+        assert_eq!(result.sequence_count, 100);
+        assert!((result.proportion_passing - 1.0).abs() < 1e-9);
+        assert!(result.proportion_ok);
+    }
+
+    #[test]
+    fn test_assess_p_values_all_failing_is_not_ok() {
+        let p_values = vec![0.001; 100];
+        let result = assess_p_values(&p_values);
+        assert!((result.proportion_passing - 0.0).abs() < 1e-9);
+        assert!(!result.proportion_ok);
+    }
+
+    #[test]
+    fn test_assess_p_values_below_uniformity_threshold_skips_chi_square() {
+        let p_values = vec![0.5; 10];
+        let result = assess_p_values(&p_values);
+        assert!(result.uniformity_chi_square.is_none());
+        assert!(result.uniformity_p_value.is_none());
+        assert!(result.uniformity_ok.is_none());
+    }
+
+    #[test]
+    fn test_assess_p_values_uniform_distribution_passes_uniformity_check() {
+        // 100 p-values spread evenly across the 10 bins should look uniform.
+        let p_values: Vec<f64> = (0..100).map(|i| (i as f64 + 0.5) / 100.0).collect();
+        let result = assess_p_values(&p_values);
+        assert!(result.uniformity_chi_square.is_some());
+        assert!(result.uniformity_ok.unwrap());
+    }
+
+    #[test]
+    fn test_assess_p_values_skewed_distribution_fails_uniformity_check() {
+        // All p-values crammed into the first bin is maximally non-uniform.
+        let p_values = vec![0.01; 100];
+        let result = assess_p_values(&p_values);
+        assert!(result.uniformity_chi_square.is_some());
+        assert!(!result.uniformity_ok.unwrap());
+    }
+}
@@ -1,9 +1,14 @@
 use nistrs::prelude::*;
+use rayon::prelude::*;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 
 #[allow(unused_imports)]
 use tracing::{debug, error, info, warn};
 
+use crate::calibration::{self, CalibrationResult};
+use crate::compression_test::{self, CompressionResult};
+use crate::nist_second_level::{self, SecondLevelResult};
 use crate::nist_tests;
 use crate::{NistResults, NistTestResult};
 
@@ -64,14 +69,81 @@ impl TestTier {
     };
 }
 
+/// Rayon thread pools, one per distinct `worker_count` ever requested,
+/// built once and reused for the life of the process instead of per
+/// request. `NistWrapper::new()` runs on every validation request (HTTP and
+/// WS alike), so rebuilding (and tearing down) a whole OS-thread pool each
+/// time would thrash threads under load; caching by `worker_count` still
+/// lets `with_worker_count` (used by tests pinning a specific degree of
+/// parallelism) get its own pool without invalidating the default one.
+fn thread_pool_for_worker_count(worker_count: usize) -> Result<Arc<rayon::ThreadPool>, String> {
+    static POOLS: OnceLock<Mutex<HashMap<usize, Arc<rayon::ThreadPool>>>> = OnceLock::new();
+    let pools = POOLS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut pools = pools.lock().unwrap();
+
+    if let Some(pool) = pools.get(&worker_count) {
+        return Ok(Arc::clone(pool));
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(worker_count)
+        .build()
+        .map_err(|e| format!("failed to build NIST worker thread pool: {}", e))?;
+    let pool = Arc::new(pool);
+    pools.insert(worker_count, Arc::clone(&pool));
+    Ok(pool)
+}
+
+/// `calibration::calibrate` results, one per distinct bit count ever
+/// annotated, cached for the life of the process - mirrors
+/// `thread_pool_for_worker_count`'s cache shape. A calibration run is
+/// `2 * SAMPLES_PER_GENERATOR` full NIST battery runs (see `calibration`'s
+/// module doc), so this only pays that cost once per size instead of once
+/// per annotated request.
+fn calibration_for_bit_count(bit_count: usize, tier_level: u8) -> Result<Arc<CalibrationResult>, String> {
+    static CALIBRATIONS: OnceLock<Mutex<HashMap<usize, Arc<CalibrationResult>>>> = OnceLock::new();
+    let calibrations = CALIBRATIONS.get_or_init(|| Mutex::new(HashMap::new()));
+
+    {
+        let calibrations = calibrations.lock().unwrap();
+        if let Some(result) = calibrations.get(&bit_count) {
+            return Ok(Arc::clone(result));
+        }
+    }
+
+    let result = Arc::new(calibration::calibrate(bit_count, tier_level)?);
+    calibrations.lock().unwrap().insert(bit_count, Arc::clone(&result));
+    Ok(result)
+}
+
 /// Wrapper for NIST Statistical Test Suite using nistrs crate
 pub struct NistWrapper {
-    // No need for paths anymore - tests run in-memory
+    /// How many threads `run_all_tests` spreads the per-test computations
+    /// across. Each NIST test only reads the same immutable `BitsData`, so
+    /// splitting them across a pool scales wall-clock time with core count
+    /// on megabit-scale inputs, without affecting the result.
+    worker_count: usize,
 }
 
 impl NistWrapper {
     pub fn new() -> Self {
-        NistWrapper {}
+        Self::with_worker_count(Self::default_worker_count())
+    }
+
+    /// Build a wrapper that runs the NIST battery across exactly
+    /// `worker_count` threads, instead of the default (the number of
+    /// logical CPUs). Exposed so callers - and tests asserting reproducible
+    /// results - can pin a specific degree of parallelism.
+    pub fn with_worker_count(worker_count: usize) -> Self {
+        NistWrapper {
+            worker_count: worker_count.max(1),
+        }
+    }
+
+    fn default_worker_count() -> usize {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
     }
 
     /// Determine which test tier to use based on input size
@@ -101,11 +173,32 @@ impl NistWrapper {
     /// Run NIST test suite directly on the bits
     /// Returns structured test results
     pub fn run_tests(&self, bits: &[u8]) -> Result<NistResults, String> {
-        self.run_tests_structured(bits)
+        self.run_tests_structured(bits, false)
+    }
+
+    /// Same as `run_tests`, but additionally annotates the result with a
+    /// percentile comparison against `calibration::calibrate`'s empirical
+    /// known-good pass-count distribution at this bit count (see
+    /// `NistResults::calibration_percentile`) when `with_calibration` is set.
+    /// Calibration is opt-in rather than folded into `run_tests` itself
+    /// since it reruns the full battery many times per distinct bit count
+    /// (cached afterwards via `calibration_for_bit_count`) - expensive
+    /// enough that `calibration`'s own module doc calls out not wiring it
+    /// into every validation by default.
+    pub fn run_tests_with_calibration(&self, bits: &[u8], with_calibration: bool) -> Result<NistResults, String> {
+        self.run_tests_structured(bits, with_calibration)
+    }
+
+    /// The tier level (1-5, or 0 if too few bits for Tier 1) that `bit_count`
+    /// would run at. `TestTier` itself stays private to this module, but the
+    /// calibration subsystem needs to confirm a caller's declared tier
+    /// matches the bit count they're requesting it for.
+    pub(crate) fn tier_level_for_bit_count(bit_count: usize) -> u8 {
+        Self::determine_tier(bit_count).level
     }
 
     /// Run NIST tests and return structured data
-    pub fn run_tests_structured(&self, bits: &[u8]) -> Result<NistResults, String> {
+    pub fn run_tests_structured(&self, bits: &[u8], with_calibration: bool) -> Result<NistResults, String> {
         info!("Starting NIST statistical tests with nistrs");
 
         // Determine test tier based on input size
@@ -141,10 +234,48 @@ impl NistWrapper {
         let bits_data = BitsData::from_binary(packed_bytes);
 
         // Run tests appropriate for this tier
-        let test_results = Self::run_all_tests(&bits_data, &tier);
+        let test_results = self.run_all_tests(&bits_data, &tier)?;
+
+        // Run the second-level (proportion + uniformity) assessment across
+        // equal-length subsequences, when there are enough bits for more
+        // than one subsequence at this tier's size.
+        let second_level_results = Self::run_second_level_tests(bits, &tier);
 
         // Parse results into structured format
-        self.parse_test_results(bits, test_results, &tier)
+        self.parse_test_results(bits, test_results, second_level_results, &tier, with_calibration)
+    }
+
+    /// Partition `bits` into `m = floor(N / tier.min_bits)` equal-length
+    /// subsequences and run the second-level (proportion-passing +
+    /// uniformity-of-p-values) assessment across them, per test. Returns
+    /// `None` when there isn't enough data for more than one subsequence.
+    fn run_second_level_tests(
+        bits: &[u8],
+        tier: &TestTier,
+    ) -> Option<HashMap<String, Vec<SecondLevelResult>>> {
+        if bits.len() / tier.min_bits < nist_second_level::MIN_SEQUENCES_FOR_PROPORTION {
+            return None;
+        }
+
+        let series = nist_second_level::collect_subsequence_p_values(
+            bits,
+            tier.min_bits,
+            tier.level,
+            Self::pack_bits_to_bytes,
+        );
+
+        Some(
+            series
+                .into_iter()
+                .map(|(name, p_value_series)| {
+                    let assessments = p_value_series
+                        .iter()
+                        .map(|p_values| nist_second_level::assess_p_values(p_values))
+                        .collect();
+                    (name, assessments)
+                })
+                .collect(),
+        )
     }
 
     /// Convert Vec<u8> where each element is 0 or 1 into packed bytes
@@ -174,22 +305,37 @@ impl NistWrapper {
         packed
     }
 
-    /// Run NIST tests appropriate for the given tier
-    fn run_all_tests(data: &BitsData, tier: &TestTier) -> HashMap<String, Vec<TestResultT>> {
-        let mut results = HashMap::new();
+    /// Run NIST tests appropriate for the given tier, spread across
+    /// `self.worker_count` threads. Every test only reads `data`, so this is
+    /// safe; `par_iter().collect()` preserves `test_defs`'s original order
+    /// regardless of which thread finishes first, so the resulting map (and
+    /// everything derived from it) stays reproducible across runs and
+    /// worker counts.
+    fn run_all_tests(
+        &self,
+        data: &BitsData,
+        tier: &TestTier,
+    ) -> Result<HashMap<String, Vec<TestResultT>>, String> {
         let bit_count = data.len();
 
-        // Get all test definitions and filter by tier and bit requirements
-        for test_def in nist_tests::get_all_tests() {
-            if test_def.should_run(tier.level, bit_count) {
-                let test_results = (test_def.execute)(data);
-                if !test_results.is_empty() {
-                    results.insert(test_def.name.to_string(), test_results);
-                }
-            }
-        }
+        let test_defs: Vec<_> = nist_tests::get_all_tests()
+            .into_iter()
+            .filter(|test_def| test_def.should_run(tier.level, bit_count))
+            .collect();
 
-        results
+        let pool = thread_pool_for_worker_count(self.worker_count)?;
+
+        let per_test_results: Vec<(String, Vec<TestResultT>)> = pool.install(|| {
+            test_defs
+                .par_iter()
+                .map(|test_def| (test_def.name.to_string(), (test_def.execute)(data)))
+                .collect()
+        });
+
+        Ok(per_test_results
+            .into_iter()
+            .filter(|(_, results)| !results.is_empty())
+            .collect())
     }
 
     /// Calculate quality score from individual test results
@@ -268,7 +414,9 @@ impl NistWrapper {
         &self,
         bits: &[u8],
         test_results: HashMap<String, Vec<TestResultT>>,
+        second_level_results: Option<HashMap<String, Vec<SecondLevelResult>>>,
         tier: &TestTier,
+        with_calibration: bool,
     ) -> Result<NistResults, String> {
         let bit_count = bits.len();
 
@@ -284,17 +432,31 @@ impl NistWrapper {
                     test_name.clone()
                 };
 
+                let second_level = second_level_results
+                    .as_ref()
+                    .and_then(|map| map.get(test_name))
+                    .and_then(|assessments| assessments.get(i));
+
                 individual_tests.push(NistTestResult {
                     name,
                     passed: *passed,
                     p_value: *p_value,
                     p_values: vec![*p_value],
                     description: format!("P-value: {:.4}", p_value),
-                    metrics: None,
+                    metrics: second_level.map(Self::second_level_metrics),
                 });
             }
         }
 
+        // Compression-ratio incompressibility check, folded in alongside the
+        // nistrs-backed battery above - see `compression_test` for why a
+        // packed stream that shrinks under a simple dictionary coder is
+        // evidence of structure the p-value tests can miss on short inputs.
+        let packed_bytes = Self::pack_bits_to_bytes(bits);
+        if let Some(compression) = compression_test::compression_test(&packed_bytes) {
+            individual_tests.push(Self::compression_test_result(&compression));
+        }
+
         // Sort tests by name for consistent display
         individual_tests.sort_by(|a, b| a.name.cmp(&b.name));
 
@@ -317,6 +479,23 @@ impl NistWrapper {
             tier.level, success_count, total_tests, success_rate, avg_p_value
         );
 
+        // Compare against a known-good generator's empirical pass-count
+        // distribution at this exact bit count, when requested. Skipped by
+        // default (and always for calibration's own internal reference
+        // runs, which always call `run_tests` - see `run_tests_with_calibration`)
+        // since a calibration run itself reruns the full battery many times.
+        let calibration_percentile = if with_calibration {
+            match calibration_for_bit_count(bit_count, tier.level) {
+                Ok(calibration) => Some(calibration.describe_pass_count(success_count)),
+                Err(e) => {
+                    debug!("Skipping calibration percentile annotation: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         Ok(NistResults {
             bit_count,
             tests_passed: success_count,
@@ -325,9 +504,69 @@ impl NistWrapper {
             individual_tests,
             fallback_message: None,
             raw_output: Some(raw_output),
+            calibration_percentile,
         })
     }
 
+    /// Convert a `CompressionResult` into a `NistTestResult`. There's no
+    /// p-value here, so the compression ratio (clamped to `[0, 1]`) stands
+    /// in for one - it keeps this result comparable with the real NIST
+    /// tests' averaging in `calculate_quality_score` without claiming a
+    /// statistical significance this check doesn't compute.
+    fn compression_test_result(result: &CompressionResult) -> NistTestResult {
+        let pseudo_p_value = result.ratio.clamp(0.0, 1.0);
+        NistTestResult {
+            name: "Compression".to_string(),
+            passed: result.passed,
+            p_value: pseudo_p_value,
+            p_values: vec![pseudo_p_value],
+            description: format!(
+                "{} bytes compressed to {} bytes (ratio {:.4}, threshold {:.2})",
+                result.original_len,
+                result.compressed_len,
+                result.ratio,
+                compression_test::PASS_RATIO_THRESHOLD
+            ),
+            metrics: Some(vec![
+                ("original_bytes".to_string(), result.original_len.to_string()),
+                ("compressed_bytes".to_string(), result.compressed_len.to_string()),
+                ("ratio".to_string(), format!("{:.4}", result.ratio)),
+            ]),
+        }
+    }
+
+    /// Convert a `SecondLevelResult` into the `(key, value)` pairs surfaced
+    /// via `NistTestResult.metrics`.
+    fn second_level_metrics(result: &SecondLevelResult) -> Vec<(String, String)> {
+        let mut metrics = vec![
+            ("sequence_count".to_string(), result.sequence_count.to_string()),
+            (
+                "proportion_passing".to_string(),
+                format!("{:.4}", result.proportion_passing),
+            ),
+            (
+                "proportion_range".to_string(),
+                format!(
+                    "[{:.4}, {:.4}]",
+                    result.proportion_range.0, result.proportion_range.1
+                ),
+            ),
+            ("proportion_ok".to_string(), result.proportion_ok.to_string()),
+        ];
+
+        if let (Some(chi_square), Some(p_value), Some(ok)) = (
+            result.uniformity_chi_square,
+            result.uniformity_p_value,
+            result.uniformity_ok,
+        ) {
+            metrics.push(("uniformity_chi_square".to_string(), format!("{:.4}", chi_square)));
+            metrics.push(("uniformity_p_value".to_string(), format!("{:.6}", p_value)));
+            metrics.push(("uniformity_ok".to_string(), ok.to_string()));
+        }
+
+        metrics
+    }
+
     /// Generate raw output text for display
     fn generate_raw_output(
         bit_count: usize,
@@ -362,10 +601,22 @@ impl NistWrapper {
                 test.name,
                 test.p_value
             ));
+            if let Some(metrics) = &test.metrics {
+                let metrics_str: Vec<String> = metrics
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect();
+                output.push_str(&format!("      second-level: {}\n", metrics_str.join(", ")));
+            }
         }
 
         output.push_str("\n\nAll tests use significance level α = 0.01\n");
         output.push_str("Tests pass if p-value ≥ 0.01\n\n");
+        output.push_str(
+            "Second-level assessment (when shown) evaluates this test across equal-length \n\
+             subsequences: proportion_passing must fall within proportion_range, and (for\n\
+             m >= 55 subsequences) uniformity_p_value must be >= 0.0001.\n\n",
+        );
 
         // Add tier guidance
         output.push_str("Test Coverage:\n");
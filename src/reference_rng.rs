@@ -0,0 +1,238 @@
+//! Reference-RNG baseline generation and comparison.
+//!
+//! When a user's sequence "fails" a test, the natural question is whether
+//! that's genuine non-randomness or just small-sample noise. This module
+//! answers it directly: generate a deterministic bit stream of the same
+//! length from a trusted generator (`rand_chacha`'s ChaCha8/12/20, or
+//! `rand_pcg`'s Pcg32/Pcg64/Pcg64Mcg), run the identical test battery on it,
+//! and report per-test pass/fail deltas against the user's input. This is a
+//! single side-by-side comparison; see `calibration` for the Monte-Carlo
+//! percentile version of the same idea across many reference runs.
+
+use serde::{Deserialize, Serialize};
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::{ChaCha12Rng, ChaCha20Rng, ChaCha8Rng};
+use rand_pcg::{Pcg32, Pcg64, Pcg64Mcg};
+
+use crate::enhanced_stats::{self, EnhancedTestResults};
+use crate::nist_wrapper::NistWrapper;
+use crate::NistResults;
+
+/// Reference generator selection for `generate_reference`/`compare_against_references`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RngKind {
+    ChaCha8,
+    ChaCha12,
+    ChaCha20,
+    Pcg32,
+    Pcg64,
+    Pcg64Mcg,
+}
+
+impl RngKind {
+    fn fill_bytes(&self, seed: u64, bytes: &mut [u8]) {
+        match self {
+            RngKind::ChaCha8 => ChaCha8Rng::seed_from_u64(seed).fill_bytes(bytes),
+            RngKind::ChaCha12 => ChaCha12Rng::seed_from_u64(seed).fill_bytes(bytes),
+            RngKind::ChaCha20 => ChaCha20Rng::seed_from_u64(seed).fill_bytes(bytes),
+            RngKind::Pcg32 => Pcg32::seed_from_u64(seed).fill_bytes(bytes),
+            RngKind::Pcg64 => Pcg64::seed_from_u64(seed).fill_bytes(bytes),
+            RngKind::Pcg64Mcg => Pcg64Mcg::seed_from_u64(seed).fill_bytes(bytes),
+        }
+    }
+}
+
+/// Generate `bits` bits (the crate's 0/1-per-element bit vector convention)
+/// from `kind`, seeded with `seed` for reproducibility.
+pub fn generate_reference(kind: RngKind, seed: u64, bits: usize) -> Vec<u8> {
+    let byte_count = bits.div_ceil(8);
+    let mut bytes = vec![0u8; byte_count];
+    kind.fill_bytes(seed, &mut bytes);
+
+    let mut out = Vec::with_capacity(bits);
+    'bytes: for byte in bytes {
+        for i in (0..8).rev() {
+            if out.len() == bits {
+                break 'bytes;
+            }
+            out.push((byte >> i) & 1);
+        }
+    }
+    out
+}
+
+/// Per-test pass/fail delta between the input and one reference generator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestDelta {
+    pub test_name: String,
+    pub input_passed: bool,
+    pub reference_passed: bool,
+    /// `true` when the input failed a test that the reference generator
+    /// passed at the same bit count — the strongest signal of genuine
+    /// non-randomness rather than small-sample noise.
+    pub input_only_failure: bool,
+}
+
+/// One reference generator's run against the same bit count as the input,
+/// plus its per-test deltas against the input.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferenceRun {
+    pub kind: RngKind,
+    pub enhanced_results: EnhancedTestResults,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nist_results: Option<NistResults>,
+    pub deltas: Vec<TestDelta>,
+}
+
+/// Comparison of the user's input against one or more reference generators
+/// of the same bit length.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferenceComparison {
+    pub bit_count: usize,
+    pub input_enhanced_results: EnhancedTestResults,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_nist_results: Option<NistResults>,
+    pub references: Vec<ReferenceRun>,
+}
+
+/// Compare `bits` against one reference run (seeded with `seed`) from each of
+/// `kinds`, running both the enhanced small-dataset battery and (when there
+/// are enough bits) the Tier NIST battery on the input and every reference.
+pub fn compare_against_references(
+    bits: &[u8],
+    kinds: &[RngKind],
+    seed: u64,
+) -> ReferenceComparison {
+    let bit_count = bits.len();
+    let wrapper = NistWrapper::new();
+
+    let input_enhanced_results = enhanced_stats::run_enhanced_tests_structured(bits);
+    let input_nist_results = wrapper.run_tests(bits).ok();
+
+    let references = kinds
+        .iter()
+        .map(|&kind| {
+            let reference_bits = generate_reference(kind, seed, bit_count);
+            let enhanced_results = enhanced_stats::run_enhanced_tests_structured(&reference_bits);
+            let nist_results = wrapper.run_tests(&reference_bits).ok();
+
+            let deltas = build_deltas(
+                &input_enhanced_results,
+                &enhanced_results,
+                input_nist_results.as_ref(),
+                nist_results.as_ref(),
+            );
+
+            ReferenceRun {
+                kind,
+                enhanced_results,
+                nist_results,
+                deltas,
+            }
+        })
+        .collect();
+
+    ReferenceComparison {
+        bit_count,
+        input_enhanced_results,
+        input_nist_results,
+        references,
+    }
+}
+
+/// Build per-test deltas between the input's and a reference's test results,
+/// from both the enhanced battery (always present) and the NIST battery
+/// (only when both sides had enough bits to run it).
+fn build_deltas(
+    input_enhanced: &EnhancedTestResults,
+    reference_enhanced: &EnhancedTestResults,
+    input_nist: Option<&NistResults>,
+    reference_nist: Option<&NistResults>,
+) -> Vec<TestDelta> {
+    let mut deltas: Vec<TestDelta> = input_enhanced
+        .individual_tests
+        .iter()
+        .zip(reference_enhanced.individual_tests.iter())
+        .map(|(input_test, reference_test)| TestDelta {
+            test_name: input_test.test_name.clone(),
+            input_passed: input_test.passed,
+            reference_passed: reference_test.passed,
+            input_only_failure: !input_test.passed && reference_test.passed,
+        })
+        .collect();
+
+    if let (Some(input_nist), Some(reference_nist)) = (input_nist, reference_nist) {
+        for input_test in &input_nist.individual_tests {
+            if let Some(reference_test) = reference_nist
+                .individual_tests
+                .iter()
+                .find(|t| t.name == input_test.name)
+            {
+                deltas.push(TestDelta {
+                    test_name: input_test.name.clone(),
+                    input_passed: input_test.passed,
+                    reference_passed: reference_test.passed,
+                    input_only_failure: !input_test.passed && reference_test.passed,
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_reference_respects_bit_count() {
+        let bits = generate_reference(RngKind::ChaCha20, 42, 37);
+        assert_eq!(bits.len(), 37);
+        assert!(bits.iter().all(|&b| b == 0 || b == 1));
+    }
+
+    #[test]
+    fn test_generate_reference_deterministic_for_same_seed() {
+        let a = generate_reference(RngKind::Pcg64, 7, 256);
+        let b = generate_reference(RngKind::Pcg64, 7, 256);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_generate_reference_differs_across_kinds() {
+        let chacha = generate_reference(RngKind::ChaCha20, 7, 256);
+        let pcg = generate_reference(RngKind::Pcg64, 7, 256);
+        assert_ne!(chacha, pcg);
+    }
+
+    #[test]
+    fn test_compare_against_references_flags_input_only_failure() {
+        // All-zeros is maximally non-random and should fail tests a
+        // reference generator of the same length passes.
+        let bits = vec![0u8; 256];
+        let comparison =
+            compare_against_references(&bits, &[RngKind::ChaCha20], 1);
+
+        assert_eq!(comparison.references.len(), 1);
+        let reference = &comparison.references[0];
+        assert!(reference
+            .deltas
+            .iter()
+            .any(|d| d.input_only_failure));
+    }
+
+    #[test]
+    fn test_compare_against_references_multiple_kinds() {
+        let bits = generate_reference(RngKind::ChaCha20, 99, 256);
+        let comparison = compare_against_references(
+            &bits,
+            &[RngKind::ChaCha8, RngKind::Pcg32, RngKind::Pcg64Mcg],
+            1,
+        );
+        assert_eq!(comparison.references.len(), 3);
+        assert_eq!(comparison.bit_count, 256);
+    }
+}
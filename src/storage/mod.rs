@@ -0,0 +1,175 @@
+//! Pluggable storage backend for query/test-result history.
+//!
+//! The Postgres-only path made the validator hard to run anywhere without
+//! standing up an external database, and hard to test without one too. This
+//! module defines the storage operations as a `ResultStore` trait, with a
+//! Postgres implementation (for deployments that already run one) and a
+//! SQLite implementation (for everyone else, including fully in-memory test
+//! runs). `StorageConfig` is the single switch between the two; callers
+//! never need to know which backend they got beyond that.
+
+mod postgres;
+mod sqlite;
+
+pub use postgres::PostgresStore;
+pub use sqlite::SqliteStore;
+
+use crate::analytics::AnalyticsSummary;
+use crate::NistTestResult;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// A single (test name, passed, p-value) row, as returned by
+/// `join_results_for_query` - the same shape the normalized schema's
+/// `test_results JOIN test_definitions` query has always returned.
+pub type TestResultRow = (String, bool, f64);
+
+/// Snapshot of a connection pool's current size and in-use count, as
+/// reported by the underlying `sqlx::Pool`. Used by the `/health` endpoint
+/// to show an orchestrator or load balancer more than a bare up/down bit.
+#[derive(Debug, Clone, Copy)]
+pub struct PoolStatus {
+    pub size: u32,
+    pub in_use: u32,
+}
+
+/// Storage operations needed to log validator queries and their individual
+/// NIST test results, independent of which database backend they land in.
+#[async_trait]
+pub trait ResultStore: Send + Sync {
+    /// Run this backend's migrations (schema creation plus the NIST test
+    /// definitions seed), idempotently.
+    async fn run_migrations(&self) -> Result<(), String>;
+
+    /// Insert a `queries` row for a single validation request.
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_query(
+        &self,
+        query_id: Uuid,
+        client_ip: &str,
+        user_agent: &str,
+        country: Option<&str>,
+        numbers_sample: &str,
+        numbers_truncated: bool,
+        total_numbers_count: i32,
+        total_bits_count: i32,
+        valid: bool,
+        quality_score: f64,
+        processing_time_ms: i32,
+    ) -> Result<(), String>;
+
+    /// Insert (or update, on a repeat query/test pair) a `test_results` row,
+    /// getting-or-creating the matching `test_definitions` row first.
+    async fn insert_test_result(
+        &self,
+        query_id: Uuid,
+        test_result: &NistTestResult,
+    ) -> Result<(), String>;
+
+    /// Fetch every test result logged for `query_id`, joined with its test
+    /// name, ordered by test name.
+    async fn join_results_for_query(&self, query_id: Uuid) -> Result<Vec<TestResultRow>, String>;
+
+    /// Summarize every query/test result logged in `[window_start, window_end)`:
+    /// per-test pass rates, a p-value histogram, and quality-score/processing-time
+    /// statistics. This is the aggregate counterpart to `join_results_for_query`,
+    /// which only ever looks at one query at a time.
+    async fn analytics_summary(
+        &self,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+    ) -> Result<AnalyticsSummary, String>;
+
+    /// Run a trivial query against the backend to confirm the pool can still
+    /// reach it. A pool that connected fine at startup can still go bad
+    /// later (a failover, a dropped replica) while `acquire()` keeps handing
+    /// out connections that fail the moment a real query runs - this is the
+    /// cheap way to notice that before a request does.
+    async fn health_check(&self) -> Result<(), String>;
+
+    /// Report the pool's current size and in-use connection count. Cheap and
+    /// synchronous - unlike `health_check`, it never touches the network.
+    fn pool_status(&self) -> PoolStatus;
+
+    /// Drain in-flight queries and close every pooled connection. Call this
+    /// during shutdown so the runtime isn't torn down out from under a
+    /// connection that's mid-query.
+    async fn terminate(&self);
+}
+
+/// Tunable connection-pool limits, shared by both backends. `connect` uses
+/// `PoolSettings::default()`; `connect_with_settings` lets callers that know
+/// their expected load (or want a tighter acquire timeout for failover
+/// detection) override it.
+#[derive(Debug, Clone)]
+pub struct PoolSettings {
+    pub min_connections: u32,
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    /// How often `spawn_health_check_loop` should probe the backend.
+    pub health_check_interval: Duration,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        PoolSettings {
+            min_connections: 0,
+            max_connections: 5,
+            acquire_timeout: Duration::from_secs(30),
+            health_check_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Which backend to connect to, and how. A single config value is the only
+/// thing that distinguishes "log to Postgres" from "log to an embedded
+/// SQLite file (or `:memory:` for tests)".
+pub enum StorageConfig {
+    Postgres(String),
+    Sqlite(String),
+}
+
+/// Connect to the configured backend with default pool settings and run its
+/// migrations.
+pub async fn connect(config: StorageConfig) -> Result<Box<dyn ResultStore>, String> {
+    connect_with_settings(config, PoolSettings::default()).await
+}
+
+/// Connect to the configured backend with explicit pool settings and run its
+/// migrations.
+pub async fn connect_with_settings(
+    config: StorageConfig,
+    settings: PoolSettings,
+) -> Result<Box<dyn ResultStore>, String> {
+    let store: Box<dyn ResultStore> = match config {
+        StorageConfig::Postgres(url) => Box::new(PostgresStore::connect(&url, &settings).await?),
+        StorageConfig::Sqlite(url) => Box::new(SqliteStore::connect(&url, &settings).await?),
+    };
+    store.run_migrations().await?;
+    Ok(store)
+}
+
+/// Spawn a background task that calls `health_check` on `store` every
+/// `settings.health_check_interval`, logging (but not panicking on) probe
+/// failures. Returns the task handle so callers can abort it during
+/// shutdown, e.g. right before calling `store.terminate()`.
+pub fn spawn_health_check_loop(
+    store: Arc<dyn ResultStore>,
+    settings: &PoolSettings,
+) -> tokio::task::JoinHandle<()> {
+    let interval = settings.health_check_interval;
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match store.health_check().await {
+                Ok(()) => debug!("Storage backend health check passed"),
+                Err(e) => warn!("Storage backend health check failed: {}", e),
+            }
+        }
+    })
+}
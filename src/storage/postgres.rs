@@ -0,0 +1,281 @@
+//! `ResultStore` implementation backed by PostgreSQL.
+//!
+//! Queries go through `sqlx::query!`/`query_as!` rather than the stringly
+//! bound `sqlx::query()`/`query_as()`, so a column rename or type change in
+//! `migrations/postgres/` is a build-time error here instead of a runtime
+//! one. That requires either a live `DATABASE_URL` at compile time or the
+//! checked-query cache under `.sqlx/` - CI uses
+//! the cache so the crate still builds with no database reachable.
+
+use super::{PoolSettings, PoolStatus, ResultStore, TestResultRow};
+use crate::analytics::{p_value_buckets, percentile, AnalyticsSummary, TestPassRate};
+use crate::NistTestResult;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct PostgresStore {
+    pool: PgPool,
+}
+
+impl PostgresStore {
+    pub async fn connect(database_url: &str, settings: &PoolSettings) -> Result<Self, String> {
+        let pool = PgPoolOptions::new()
+            .min_connections(settings.min_connections)
+            .max_connections(settings.max_connections)
+            .acquire_timeout(settings.acquire_timeout)
+            .connect(database_url)
+            .await
+            .map_err(|e| format!("Failed to connect to Postgres: {}", e))?;
+        Ok(PostgresStore { pool })
+    }
+}
+
+#[async_trait]
+impl ResultStore for PostgresStore {
+    async fn run_migrations(&self) -> Result<(), String> {
+        sqlx::migrate!("./migrations/postgres")
+            .run(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to run Postgres migrations: {}", e))
+    }
+
+    async fn insert_query(
+        &self,
+        query_id: Uuid,
+        client_ip: &str,
+        user_agent: &str,
+        country: Option<&str>,
+        numbers_sample: &str,
+        numbers_truncated: bool,
+        total_numbers_count: i32,
+        total_bits_count: i32,
+        valid: bool,
+        quality_score: f64,
+        processing_time_ms: i32,
+    ) -> Result<(), String> {
+        sqlx::query!(
+            r#"
+            INSERT INTO queries (
+                query_id, created_at, client_ip, user_agent, country,
+                numbers_sample, numbers_truncated, total_numbers_count, total_bits_count,
+                valid, quality_score, nist_used,
+                processing_time_ms, error_message
+            ) VALUES (
+                $1, NOW(), $2, $3, $4,
+                $5, $6, $7, $8,
+                $9, $10, true,
+                $11, NULL
+            )
+            "#,
+            query_id,
+            client_ip,
+            user_agent,
+            country,
+            numbers_sample,
+            numbers_truncated,
+            total_numbers_count,
+            total_bits_count,
+            valid,
+            quality_score,
+            processing_time_ms,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to insert query: {}", e))?;
+        Ok(())
+    }
+
+    async fn insert_test_result(
+        &self,
+        query_id: Uuid,
+        test_result: &NistTestResult,
+    ) -> Result<(), String> {
+        let test_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO test_definitions (test_name, description)
+            VALUES ($1, $2)
+            ON CONFLICT (test_name) DO UPDATE SET test_name = EXCLUDED.test_name
+            RETURNING id
+            "#,
+            test_result.name,
+            test_result.description,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to get or create test definition: {}", e))?;
+
+        let p_values_json = serde_json::to_value(&test_result.p_values)
+            .map_err(|e| format!("Failed to serialize p_values: {}", e))?;
+        let metrics_json = match &test_result.metrics {
+            Some(metrics) => serde_json::to_value(metrics)
+                .map_err(|e| format!("Failed to serialize metrics: {}", e))?,
+            None => serde_json::Value::Null,
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO test_results (query_id, test_id, passed, p_value, p_values, metrics)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (query_id, test_id) DO UPDATE SET
+                passed = EXCLUDED.passed,
+                p_value = EXCLUDED.p_value,
+                p_values = EXCLUDED.p_values,
+                metrics = EXCLUDED.metrics
+            "#,
+            query_id,
+            test_id,
+            test_result.passed,
+            test_result.p_value,
+            p_values_json,
+            metrics_json,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to insert test result: {}", e))?;
+        Ok(())
+    }
+
+    async fn join_results_for_query(&self, query_id: Uuid) -> Result<Vec<TestResultRow>, String> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT td.test_name, tr.passed, tr.p_value
+            FROM test_results tr
+            JOIN test_definitions td ON tr.test_id = td.id
+            WHERE tr.query_id = $1
+            ORDER BY td.test_name
+            "#,
+            query_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to join test results: {}", e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.test_name, row.passed, row.p_value))
+            .collect())
+    }
+
+    async fn analytics_summary(
+        &self,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+    ) -> Result<AnalyticsSummary, String> {
+        let query_rows = sqlx::query!(
+            r#"
+            SELECT quality_score, processing_time_ms, nist_used
+            FROM queries
+            WHERE created_at >= $1::timestamptz AND created_at < $2::timestamptz
+            "#,
+            window_start,
+            window_end,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch queries for analytics: {}", e))?;
+
+        let total_queries = query_rows.len() as i64;
+        let nist_used_count = query_rows.iter().filter(|r| r.nist_used).count() as i64;
+
+        let mut quality_scores: Vec<f64> = query_rows.iter().map(|r| r.quality_score).collect();
+        quality_scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mean_quality_score = if quality_scores.is_empty() {
+            None
+        } else {
+            Some(quality_scores.iter().sum::<f64>() / quality_scores.len() as f64)
+        };
+
+        let mut processing_times: Vec<f64> = query_rows
+            .iter()
+            .map(|r| r.processing_time_ms as f64)
+            .collect();
+        processing_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mean_processing_time_ms = if processing_times.is_empty() {
+            None
+        } else {
+            Some(processing_times.iter().sum::<f64>() / processing_times.len() as f64)
+        };
+
+        let pass_rate_rows = sqlx::query!(
+            r#"
+            SELECT td.test_name, COUNT(*) as "total!", COUNT(*) FILTER (WHERE tr.passed) as "passed!"
+            FROM test_results tr
+            JOIN test_definitions td ON tr.test_id = td.id
+            JOIN queries q ON tr.query_id = q.query_id
+            WHERE q.created_at >= $1::timestamptz AND q.created_at < $2::timestamptz
+            GROUP BY td.test_name
+            ORDER BY td.test_name
+            "#,
+            window_start,
+            window_end,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch test pass rates for analytics: {}", e))?;
+
+        let test_pass_rates = pass_rate_rows
+            .into_iter()
+            .map(|row| TestPassRate {
+                test_name: row.test_name,
+                total: row.total,
+                passed: row.passed,
+                pass_rate: if row.total > 0 {
+                    row.passed as f64 / row.total as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+
+        let p_value_rows = sqlx::query!(
+            r#"
+            SELECT tr.p_value
+            FROM test_results tr
+            JOIN queries q ON tr.query_id = q.query_id
+            WHERE q.created_at >= $1::timestamptz AND q.created_at < $2::timestamptz
+            "#,
+            window_start,
+            window_end,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch p-values for analytics: {}", e))?;
+
+        let p_values: Vec<f64> = p_value_rows.into_iter().map(|row| row.p_value).collect();
+
+        Ok(AnalyticsSummary {
+            window_start,
+            window_end,
+            total_queries,
+            mean_quality_score,
+            p50_quality_score: percentile(&quality_scores, 0.5),
+            p95_quality_score: percentile(&quality_scores, 0.95),
+            nist_used_count,
+            mean_processing_time_ms,
+            p95_processing_time_ms: percentile(&processing_times, 0.95),
+            test_pass_rates,
+            p_value_buckets: p_value_buckets(&p_values),
+        })
+    }
+
+    async fn health_check(&self) -> Result<(), String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Postgres health check failed: {}", e))?;
+        Ok(())
+    }
+
+    async fn terminate(&self) {
+        self.pool.close().await;
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        let size = self.pool.size();
+        let in_use = size.saturating_sub(self.pool.num_idle() as u32);
+        PoolStatus { size, in_use }
+    }
+}
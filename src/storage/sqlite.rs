@@ -0,0 +1,366 @@
+//! `ResultStore` implementation backed by embedded SQLite.
+//!
+//! Runs against a file on disk (with WAL journaling, so concurrent readers
+//! don't block a writer) or fully in-memory via a `sqlite::memory:` URL,
+//! which is what lets the integration tests exercise the real schema
+//! without any server. In-memory pools are capped at a single connection:
+//! each new SQLite connection to `:memory:` otherwise gets its own, separate
+//! empty database, which would silently lose every prior write.
+//!
+//! Like `postgres.rs`, queries go through `sqlx::query!`/`query_as!` for
+//! compile-time column/type checking against `migrations/sqlite/`. SQLite's
+//! columns are dynamically typed, so a few columns need the `as "col: Type"`
+//! override syntax to tell the macro what Rust type to decode them as.
+
+use super::{PoolSettings, PoolStatus, ResultStore, TestResultRow};
+use crate::analytics::{p_value_buckets, percentile, AnalyticsSummary, TestPassRate};
+use crate::NistTestResult;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Format a timestamp to match `datetime('now')`'s own output
+/// (`YYYY-MM-DD HH:MM:SS`, plus a fractional-seconds suffix only when `ts`
+/// has a non-zero fraction), so a window comparison against `created_at`
+/// sorts lexically the same way it sorts chronologically - SQLite stores
+/// timestamps as plain TEXT, so a `T`-separated RFC 3339 string would
+/// otherwise compare incorrectly against the `' '`-separated default format.
+fn sqlite_timestamp(ts: DateTime<Utc>) -> String {
+    ts.format("%Y-%m-%d %H:%M:%S%.f").to_string()
+}
+
+pub struct SqliteStore {
+    pool: SqlitePool,
+}
+
+impl SqliteStore {
+    pub async fn connect(database_url: &str, settings: &PoolSettings) -> Result<Self, String> {
+        let is_memory = database_url.contains(":memory:");
+
+        let mut options = SqliteConnectOptions::from_str(database_url)
+            .map_err(|e| format!("Invalid SQLite URL: {}", e))?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        if !is_memory {
+            options = options.journal_mode(SqliteJournalMode::Wal);
+        }
+
+        // A fresh :memory: database is created per connection, so more than
+        // one connection would each see an empty database - the configured
+        // pool size is overridden, not merely capped, for that case.
+        let max_connections = if is_memory { 1 } else { settings.max_connections };
+        let min_connections = settings.min_connections.min(max_connections);
+
+        let pool = SqlitePoolOptions::new()
+            .min_connections(min_connections)
+            .max_connections(max_connections)
+            .acquire_timeout(settings.acquire_timeout)
+            .connect_with(options)
+            .await
+            .map_err(|e| format!("Failed to connect to SQLite: {}", e))?;
+
+        Ok(SqliteStore { pool })
+    }
+}
+
+#[async_trait]
+impl ResultStore for SqliteStore {
+    async fn run_migrations(&self) -> Result<(), String> {
+        sqlx::migrate!("./migrations/sqlite")
+            .run(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to run SQLite migrations: {}", e))
+    }
+
+    async fn insert_query(
+        &self,
+        query_id: Uuid,
+        client_ip: &str,
+        user_agent: &str,
+        country: Option<&str>,
+        numbers_sample: &str,
+        numbers_truncated: bool,
+        total_numbers_count: i32,
+        total_bits_count: i32,
+        valid: bool,
+        quality_score: f64,
+        processing_time_ms: i32,
+    ) -> Result<(), String> {
+        let query_id = query_id.to_string();
+        sqlx::query!(
+            r#"
+            INSERT INTO queries (
+                query_id, created_at, client_ip, user_agent, country,
+                numbers_sample, numbers_truncated, total_numbers_count, total_bits_count,
+                valid, quality_score, nist_used,
+                processing_time_ms, error_message
+            ) VALUES (
+                $1, datetime('now'), $2, $3, $4,
+                $5, $6, $7, $8,
+                $9, $10, 1,
+                $11, NULL
+            )
+            "#,
+            query_id,
+            client_ip,
+            user_agent,
+            country,
+            numbers_sample,
+            numbers_truncated,
+            total_numbers_count,
+            total_bits_count,
+            valid,
+            quality_score,
+            processing_time_ms,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to insert query: {}", e))?;
+        Ok(())
+    }
+
+    async fn insert_test_result(
+        &self,
+        query_id: Uuid,
+        test_result: &NistTestResult,
+    ) -> Result<(), String> {
+        let test_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO test_definitions (test_name, description)
+            VALUES ($1, $2)
+            ON CONFLICT (test_name) DO UPDATE SET test_name = excluded.test_name
+            RETURNING id
+            "#,
+            test_result.name,
+            test_result.description,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to get or create test definition: {}", e))?;
+
+        let p_values_json = serde_json::to_string(&test_result.p_values)
+            .map_err(|e| format!("Failed to serialize p_values: {}", e))?;
+        let metrics_json = match &test_result.metrics {
+            Some(metrics) => Some(
+                serde_json::to_string(metrics)
+                    .map_err(|e| format!("Failed to serialize metrics: {}", e))?,
+            ),
+            None => None,
+        };
+
+        let query_id = query_id.to_string();
+        sqlx::query!(
+            r#"
+            INSERT INTO test_results (query_id, test_id, passed, p_value, p_values, metrics)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (query_id, test_id) DO UPDATE SET
+                passed = excluded.passed,
+                p_value = excluded.p_value,
+                p_values = excluded.p_values,
+                metrics = excluded.metrics
+            "#,
+            query_id,
+            test_id,
+            test_result.passed,
+            test_result.p_value,
+            p_values_json,
+            metrics_json,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to insert test result: {}", e))?;
+        Ok(())
+    }
+
+    async fn join_results_for_query(&self, query_id: Uuid) -> Result<Vec<TestResultRow>, String> {
+        let query_id = query_id.to_string();
+        let rows = sqlx::query!(
+            r#"
+            SELECT td.test_name, tr.passed as "passed: bool", tr.p_value
+            FROM test_results tr
+            JOIN test_definitions td ON tr.test_id = td.id
+            WHERE tr.query_id = $1
+            ORDER BY td.test_name
+            "#,
+            query_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to join test results: {}", e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.test_name, row.passed, row.p_value))
+            .collect())
+    }
+
+    async fn analytics_summary(
+        &self,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+    ) -> Result<AnalyticsSummary, String> {
+        let window_start_text = sqlite_timestamp(window_start);
+        let window_end_text = sqlite_timestamp(window_end);
+
+        let query_rows = sqlx::query!(
+            r#"
+            SELECT quality_score, processing_time_ms, nist_used as "nist_used: bool"
+            FROM queries
+            WHERE created_at >= $1 AND created_at < $2
+            "#,
+            window_start_text,
+            window_end_text,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch queries for analytics: {}", e))?;
+
+        let total_queries = query_rows.len() as i64;
+        let nist_used_count = query_rows.iter().filter(|r| r.nist_used).count() as i64;
+
+        let mut quality_scores: Vec<f64> = query_rows.iter().map(|r| r.quality_score).collect();
+        quality_scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mean_quality_score = if quality_scores.is_empty() {
+            None
+        } else {
+            Some(quality_scores.iter().sum::<f64>() / quality_scores.len() as f64)
+        };
+
+        let mut processing_times: Vec<f64> = query_rows
+            .iter()
+            .map(|r| r.processing_time_ms as f64)
+            .collect();
+        processing_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mean_processing_time_ms = if processing_times.is_empty() {
+            None
+        } else {
+            Some(processing_times.iter().sum::<f64>() / processing_times.len() as f64)
+        };
+
+        let pass_rate_rows = sqlx::query!(
+            r#"
+            SELECT td.test_name,
+                COUNT(*) as "total!: i64",
+                SUM(CASE WHEN tr.passed THEN 1 ELSE 0 END) as "passed!: i64"
+            FROM test_results tr
+            JOIN test_definitions td ON tr.test_id = td.id
+            JOIN queries q ON tr.query_id = q.query_id
+            WHERE q.created_at >= $1 AND q.created_at < $2
+            GROUP BY td.test_name
+            ORDER BY td.test_name
+            "#,
+            window_start_text,
+            window_end_text,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch test pass rates for analytics: {}", e))?;
+
+        let test_pass_rates = pass_rate_rows
+            .into_iter()
+            .map(|row| TestPassRate {
+                test_name: row.test_name,
+                total: row.total,
+                passed: row.passed,
+                pass_rate: if row.total > 0 {
+                    row.passed as f64 / row.total as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+
+        let p_value_rows = sqlx::query!(
+            r#"
+            SELECT tr.p_value
+            FROM test_results tr
+            JOIN queries q ON tr.query_id = q.query_id
+            WHERE q.created_at >= $1 AND q.created_at < $2
+            "#,
+            window_start_text,
+            window_end_text,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch p-values for analytics: {}", e))?;
+
+        let p_values: Vec<f64> = p_value_rows.into_iter().map(|row| row.p_value).collect();
+
+        Ok(AnalyticsSummary {
+            window_start,
+            window_end,
+            total_queries,
+            mean_quality_score,
+            p50_quality_score: percentile(&quality_scores, 0.5),
+            p95_quality_score: percentile(&quality_scores, 0.95),
+            nist_used_count,
+            mean_processing_time_ms,
+            p95_processing_time_ms: percentile(&processing_times, 0.95),
+            test_pass_rates,
+            p_value_buckets: p_value_buckets(&p_values),
+        })
+    }
+
+    async fn health_check(&self) -> Result<(), String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("SQLite health check failed: {}", e))?;
+        Ok(())
+    }
+
+    async fn terminate(&self) {
+        self.pool.close().await;
+    }
+
+    fn pool_status(&self) -> PoolStatus {
+        let size = self.pool.size();
+        let in_use = size.saturating_sub(self.pool.num_idle() as u32);
+        PoolStatus { size, in_use }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connect_to_in_memory_database() {
+        let store = SqliteStore::connect("sqlite::memory:", &PoolSettings::default())
+            .await
+            .unwrap();
+        store.run_migrations().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_fetch_query_result_round_trip() {
+        let store = SqliteStore::connect("sqlite::memory:", &PoolSettings::default())
+            .await
+            .unwrap();
+        store.run_migrations().await.unwrap();
+
+        let query_id = Uuid::new_v4();
+        store
+            .insert_query(query_id, "127.0.0.1", "test-agent", None, "0,1,2,3", false, 4, 32, true, 0.9, 10)
+            .await
+            .unwrap();
+
+        let test_result = NistTestResult {
+            name: "Frequency (Monobit)".to_string(),
+            passed: true,
+            p_value: 0.5,
+            p_values: vec![0.5],
+            description: "desc".to_string(),
+            metrics: None,
+        };
+        store.insert_test_result(query_id, &test_result).await.unwrap();
+
+        let rows = store.join_results_for_query(query_id).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, "Frequency (Monobit)");
+        assert!(rows[0].1);
+    }
+}
@@ -4,11 +4,15 @@
 /// Set DATABASE_URL environment variable to run these tests, or they will be skipped.
 ///
 /// Example: DATABASE_URL=postgres://localhost/randomnumbervalidator_test cargo test
+use randomnumbervalidator::storage::{connect as connect_store, ResultStore, StorageConfig};
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-/// Helper function to create a test database pool
+/// Helper function to create a test database pool, for the raw schema/
+/// constraint assertions below that need to observe failures the
+/// `ResultStore` abstraction deliberately papers over (e.g. a unique
+/// constraint violation, where `insert_test_result` does an upsert).
 /// Returns None if DATABASE_URL is not set (tests will be skipped)
 async fn create_test_pool() -> Option<PgPool> {
     // Skip tests if DATABASE_URL is not set
@@ -33,7 +37,7 @@ async fn create_test_pool() -> Option<PgPool> {
     };
 
     // Run migrations
-    if let Err(e) = sqlx::migrate!("./migrations").run(&pool).await {
+    if let Err(e) = sqlx::migrate!("./migrations/postgres").run(&pool).await {
         eprintln!("Failed to run migrations: {}, skipping tests", e);
         return None;
     }
@@ -41,6 +45,30 @@ async fn create_test_pool() -> Option<PgPool> {
     Some(pool)
 }
 
+/// Helper function to create a `ResultStore` against the same test
+/// database, for the query-insertion boilerplate shared across most tests
+/// here. Returns None if DATABASE_URL is not set (tests will be skipped)
+async fn create_test_store() -> Option<Box<dyn ResultStore>> {
+    let database_url = std::env::var("DATABASE_URL").ok()?;
+    match connect_store(StorageConfig::Postgres(database_url)).await {
+        Ok(store) => Some(store),
+        Err(e) => {
+            eprintln!("Failed to connect to database: {}, skipping tests", e);
+            None
+        }
+    }
+}
+
+/// Insert a query row with the same placeholder field values every test
+/// here uses, so each test doesn't need to spell out the full 11-column
+/// binding list just to get a valid `queries` row to hang test results off.
+async fn insert_test_query(store: &dyn ResultStore, query_id: Uuid) {
+    store
+        .insert_query(query_id, "127.0.0.1", "test-agent", None, "0,1,2,3", false, 4, 32, true, 0.75, 100)
+        .await
+        .expect("Failed to insert query");
+}
+
 /// Helper function to clean up test data
 async fn cleanup_test_data(pool: &PgPool, query_id: Uuid) {
     // Delete test results first (due to foreign key)
@@ -85,33 +113,12 @@ async fn test_insert_query_without_test_results() {
     let Some(pool) = create_test_pool().await else {
         return; // Skip test if database not available
     };
+    let Some(store) = create_test_store().await else {
+        return; // Skip test if database not available
+    };
     let query_id = Uuid::new_v4();
 
-    // Insert a query
-    let result = sqlx::query(
-        r#"
-        INSERT INTO queries (
-            query_id, client_ip, user_agent, numbers_sample,
-            numbers_truncated, total_numbers_count, total_bits_count,
-            valid, quality_score, nist_used, processing_time_ms
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-        "#,
-    )
-    .bind(query_id)
-    .bind("127.0.0.1")
-    .bind("test-agent")
-    .bind("0,1,2,3")
-    .bind(false)
-    .bind(4)
-    .bind(32)
-    .bind(true)
-    .bind(0.75)
-    .bind(true)
-    .bind(100)
-    .execute(&pool)
-    .await;
-
-    assert!(result.is_ok(), "Failed to insert query");
+    insert_test_query(store.as_ref(), query_id).await;
 
     // Verify the query was inserted
     let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM queries WHERE query_id = $1")
@@ -131,33 +138,13 @@ async fn test_insert_test_definition_and_result() {
     let Some(pool) = create_test_pool().await else {
         return; // Skip test if database not available
     };
+    let Some(store) = create_test_store().await else {
+        return; // Skip test if database not available
+    };
     let query_id = Uuid::new_v4();
     let test_name = format!("Test Definition {}", Uuid::new_v4());
 
-    // First insert a query
-    sqlx::query(
-        r#"
-        INSERT INTO queries (
-            query_id, client_ip, user_agent, numbers_sample,
-            numbers_truncated, total_numbers_count, total_bits_count,
-            valid, quality_score, nist_used, processing_time_ms
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-        "#,
-    )
-    .bind(query_id)
-    .bind("127.0.0.1")
-    .bind("test-agent")
-    .bind("0,1,2,3")
-    .bind(false)
-    .bind(4)
-    .bind(32)
-    .bind(true)
-    .bind(0.75)
-    .bind(true)
-    .bind(100)
-    .execute(&pool)
-    .await
-    .expect("Failed to insert query");
+    insert_test_query(store.as_ref(), query_id).await;
 
     // Insert or get test definition
     let test_id: i32 = sqlx::query_scalar(
@@ -240,33 +227,13 @@ async fn test_cascade_delete() {
     let Some(pool) = create_test_pool().await else {
         return; // Skip test if database not available
     };
+    let Some(store) = create_test_store().await else {
+        return; // Skip test if database not available
+    };
     let query_id = Uuid::new_v4();
     let test_name = format!("Cascade Test {}", Uuid::new_v4());
 
-    // Insert query
-    sqlx::query(
-        r#"
-        INSERT INTO queries (
-            query_id, client_ip, user_agent, numbers_sample,
-            numbers_truncated, total_numbers_count, total_bits_count,
-            valid, quality_score, nist_used, processing_time_ms
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-        "#,
-    )
-    .bind(query_id)
-    .bind("127.0.0.1")
-    .bind("test-agent")
-    .bind("0,1,2,3")
-    .bind(false)
-    .bind(4)
-    .bind(32)
-    .bind(true)
-    .bind(0.75)
-    .bind(true)
-    .bind(100)
-    .execute(&pool)
-    .await
-    .expect("Failed to insert query");
+    insert_test_query(store.as_ref(), query_id).await;
 
     // Insert test definition and result
     let test_id: i32 = sqlx::query_scalar(
@@ -326,33 +293,13 @@ async fn test_unique_constraint_on_test_results() {
     let Some(pool) = create_test_pool().await else {
         return; // Skip test if database not available
     };
+    let Some(store) = create_test_store().await else {
+        return; // Skip test if database not available
+    };
     let query_id = Uuid::new_v4();
     let test_name = format!("Unique Test {}", Uuid::new_v4());
 
-    // Insert query
-    sqlx::query(
-        r#"
-        INSERT INTO queries (
-            query_id, client_ip, user_agent, numbers_sample,
-            numbers_truncated, total_numbers_count, total_bits_count,
-            valid, quality_score, nist_used, processing_time_ms
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-        "#,
-    )
-    .bind(query_id)
-    .bind("127.0.0.1")
-    .bind("test-agent")
-    .bind("0,1,2,3")
-    .bind(false)
-    .bind(4)
-    .bind(32)
-    .bind(true)
-    .bind(0.75)
-    .bind(true)
-    .bind(100)
-    .execute(&pool)
-    .await
-    .expect("Failed to insert query");
+    insert_test_query(store.as_ref(), query_id).await;
 
     // Insert test definition
     let test_id: i32 = sqlx::query_scalar(
@@ -409,32 +356,12 @@ async fn test_query_test_results_join() {
     let Some(pool) = create_test_pool().await else {
         return; // Skip test if database not available
     };
+    let Some(store) = create_test_store().await else {
+        return; // Skip test if database not available
+    };
     let query_id = Uuid::new_v4();
 
-    // Insert query
-    sqlx::query(
-        r#"
-        INSERT INTO queries (
-            query_id, client_ip, user_agent, numbers_sample,
-            numbers_truncated, total_numbers_count, total_bits_count,
-            valid, quality_score, nist_used, processing_time_ms
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-        "#,
-    )
-    .bind(query_id)
-    .bind("127.0.0.1")
-    .bind("test-agent")
-    .bind("0,1,2,3")
-    .bind(false)
-    .bind(4)
-    .bind(32)
-    .bind(true)
-    .bind(0.75)
-    .bind(true)
-    .bind(100)
-    .execute(&pool)
-    .await
-    .expect("Failed to insert query");
+    insert_test_query(store.as_ref(), query_id).await;
 
     // Insert multiple test results
     for i in 1..=3 {
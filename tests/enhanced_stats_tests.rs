@@ -1,5 +1,8 @@
 // Tests for enhanced_stats.rs
-use randomnumbervalidator::enhanced_stats::{frequency_test, runs_test, longest_run_test, run_enhanced_tests};
+use randomnumbervalidator::enhanced_stats::{
+    frequency_test, longest_run_test, run_enhanced_tests, run_enhanced_tests_structured,
+    runs_test,
+};
 
 #[test]
 fn test_frequency_test_balanced() {
@@ -22,6 +25,24 @@ fn test_runs_test() {
     assert!(result.statistic >= 0.0);
 }
 
+#[test]
+fn test_frequency_test_has_p_value() {
+    let bits = vec![0, 1, 0, 1, 0, 1, 0, 1];
+    let result = frequency_test(&bits);
+    assert!(result.p_value.is_some());
+    assert!((result.p_value.unwrap() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_runs_test_all_same_bit_fails_precondition() {
+    // All-ones has no run variation, so the runs test's precondition
+    // (proportion close to 0.5) fails and it should report p_value = 0.
+    let bits = vec![1; 16];
+    let result = runs_test(&bits);
+    assert_eq!(result.p_value, Some(0.0));
+    assert!(!result.passed);
+}
+
 #[test]
 fn test_longest_run_test() {
     let bits = vec![0, 0, 0, 0, 1, 1, 1, 0];
@@ -36,3 +57,37 @@ fn test_enhanced_tests() {
     assert!(summary.contains("Enhanced Statistical Analysis"));
     assert!(summary.contains("Tests Run"));
 }
+
+#[test]
+fn test_structured_results_include_spectral_and_entropy_tests() {
+    let bits: Vec<u8> = (0..256u32).map(|i| (i.wrapping_mul(2654435761) >> 30) as u8 & 1).collect();
+    let structured = run_enhanced_tests_structured(&bits);
+
+    assert!(structured
+        .individual_tests
+        .iter()
+        .any(|t| t.test_name == "Discrete Fourier Transform (Spectral) Test"));
+    assert!(structured
+        .individual_tests
+        .iter()
+        .any(|t| t.test_name == "Approximate Entropy Test"));
+}
+
+#[test]
+fn test_spectral_and_entropy_p_values_are_in_range() {
+    let bits = vec![1u8; 64];
+    let structured = run_enhanced_tests_structured(&bits);
+
+    for name in [
+        "Discrete Fourier Transform (Spectral) Test",
+        "Approximate Entropy Test",
+    ] {
+        let result = structured
+            .individual_tests
+            .iter()
+            .find(|t| t.test_name == name)
+            .unwrap_or_else(|| panic!("{} should run", name));
+        let p = result.p_value.expect("test should report a p-value");
+        assert!((0.0..=1.0).contains(&p), "{} p-value out of range: {}", name, p);
+    }
+}
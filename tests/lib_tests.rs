@@ -368,12 +368,24 @@ fn test_bitwidth_allows_nonzero_min() {
 
 #[test]
 fn test_bitwidth_invalid_value() {
-    // bit_width must be 8, 16, or 32
-    let result = prepare_input_for_nist_with_range_and_bitwidth("0,1,2", None, None, Some(12));
+    // bit_width must be between 1 and 32; 0 is reserved as the auto-minimal
+    // packing sentinel (and requires a range) rather than a literal width,
+    // and anything above 32 doesn't fit a u32 sample.
+    let result = prepare_input_for_nist_with_range_and_bitwidth("0,1,2", None, None, Some(40));
     assert!(result.is_err());
     let err = result.unwrap_err();
     assert!(err.contains("Invalid bit_width"));
-    assert!(err.contains("12"));
+    assert!(err.contains("40"));
+}
+
+#[test]
+fn test_bitwidth_configurable_nonstandard_width() {
+    // Arbitrary widths (not just 8/16/32) are now supported, packing each
+    // value in exactly its declared number of bits.
+    let result = prepare_input_for_nist_with_range_and_bitwidth("0,1,2,3", None, None, Some(12));
+    assert!(result.is_ok());
+    let bits = result.unwrap();
+    assert_eq!(bits.len(), 48); // 4 numbers * 12 bits
 }
 
 #[test]
@@ -499,7 +511,13 @@ fn test_validate_with_base64_format() {
         None,
         None,
         None,
+        BitOrder::MsbFirst,
         false,
+        false,
+        None,
+        None,
+        false,
+        None,
     );
 
     assert!(response.quality_score >= 0.0 && response.quality_score <= 1.0);
@@ -544,7 +562,13 @@ fn test_validate_with_debug_log() {
         None,
         None,
         None,
+        BitOrder::MsbFirst,
         true, // Enable debug logging
+        false,
+        None,
+        None,
+        false,
+        None,
     );
 
     assert!(response.debug_file.is_some());
@@ -570,7 +594,13 @@ fn test_validate_without_debug_log() {
         None,
         None,
         None,
+        BitOrder::MsbFirst,
         false, // Disable debug logging
+        false,
+        None,
+        None,
+        false,
+        None,
     );
 
     assert!(response.debug_file.is_none());
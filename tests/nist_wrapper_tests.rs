@@ -58,3 +58,39 @@ fn test_pack_bits_all_ones() {
     assert_eq!(packed.len(), 1);
     assert_eq!(packed[0], 0xFF);
 }
+
+#[test]
+fn test_results_reproducible_across_worker_counts() {
+    // Every NIST test only reads the shared bit slice, so running them
+    // single-threaded vs. spread across several workers must still produce
+    // byte-for-byte identical results.
+    let bits: Vec<u8> = (0..2_000).map(|i| ((i * 2654435761u64) % 2) as u8).collect();
+
+    let sequential = NistWrapper::with_worker_count(1)
+        .run_tests(&bits)
+        .expect("sequential run should succeed");
+    let parallel = NistWrapper::with_worker_count(8)
+        .run_tests(&bits)
+        .expect("parallel run should succeed");
+
+    assert_eq!(sequential.tests_passed, parallel.tests_passed);
+    assert_eq!(sequential.total_tests, parallel.total_tests);
+    assert!((sequential.success_rate - parallel.success_rate).abs() < 1e-12);
+
+    let sequential_names: Vec<&str> = sequential
+        .individual_tests
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect();
+    let parallel_names: Vec<&str> = parallel
+        .individual_tests
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect();
+    assert_eq!(sequential_names, parallel_names);
+
+    for (a, b) in sequential.individual_tests.iter().zip(&parallel.individual_tests) {
+        assert_eq!(a.passed, b.passed);
+        assert!((a.p_value - b.p_value).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,100 @@
+/// Integration tests for the embedded SQLite `JobQueue`.
+///
+/// Like `sqlite_storage_test.rs`, these run against `sqlite::memory:` so the
+/// full schema and visibility-timeout semantics are exercised with no
+/// external server or `DATABASE_URL`.
+use randomnumbervalidator::job_queue::{connect, JobQueue, JobQueueConfig};
+use randomnumbervalidator::{BitOrder, InputFormat, ValidationRequest};
+
+async fn test_queue() -> Box<dyn JobQueue> {
+    connect(JobQueueConfig::Sqlite("sqlite::memory:".to_string()))
+        .await
+        .expect("failed to connect to in-memory SQLite job queue")
+}
+
+fn sample_request() -> ValidationRequest {
+    ValidationRequest {
+        numbers: "0,1,2,3".to_string(),
+        input_format: InputFormat::Numbers,
+        range_min: None,
+        range_max: None,
+        bit_width: None,
+        bit_order: BitOrder::MsbFirst,
+        debug_log: false,
+        use_whitening: false,
+        packed_fields: None,
+        bit_selection: None,
+        with_calibration: false,
+        distribution_fit: None,
+    }
+}
+
+#[tokio::test]
+async fn test_read_returns_none_on_empty_queue() {
+    let queue = test_queue().await;
+    assert!(queue.read(30).await.expect("read should succeed").is_none());
+}
+
+#[tokio::test]
+async fn test_enqueue_increments_queue_length() {
+    let queue = test_queue().await;
+    queue
+        .enqueue(&sample_request())
+        .await
+        .expect("failed to enqueue job");
+
+    let metrics = queue.metrics().await.expect("failed to compute metrics");
+    assert_eq!(metrics.queue_length, 1);
+    assert_eq!(metrics.total_processed, 0);
+}
+
+#[tokio::test]
+async fn test_read_hides_job_until_visibility_timeout_elapses() {
+    let queue = test_queue().await;
+    queue
+        .enqueue(&sample_request())
+        .await
+        .expect("failed to enqueue job");
+
+    let job = queue
+        .read(60)
+        .await
+        .expect("read should succeed")
+        .expect("expected a visible job");
+    assert_eq!(job.read_ct, 1);
+
+    // Claimed but not yet archived/deleted - a second reader shouldn't see it.
+    assert!(queue.read(60).await.expect("read should succeed").is_none());
+}
+
+#[tokio::test]
+async fn test_archive_records_processed_count_and_empties_queue() {
+    let queue = test_queue().await;
+    queue
+        .enqueue(&sample_request())
+        .await
+        .expect("failed to enqueue job");
+
+    let job = queue.read(30).await.unwrap().unwrap();
+    queue.archive(&job).await.expect("failed to archive job");
+
+    let metrics = queue.metrics().await.expect("failed to compute metrics");
+    assert_eq!(metrics.queue_length, 0);
+    assert_eq!(metrics.total_processed, 1);
+}
+
+#[tokio::test]
+async fn test_delete_drops_job_without_counting_as_processed() {
+    let queue = test_queue().await;
+    queue
+        .enqueue(&sample_request())
+        .await
+        .expect("failed to enqueue job");
+
+    let job = queue.read(30).await.unwrap().unwrap();
+    queue.delete(job.msg_id).await.expect("failed to delete job");
+
+    let metrics = queue.metrics().await.expect("failed to compute metrics");
+    assert_eq!(metrics.queue_length, 0);
+    assert_eq!(metrics.total_processed, 0);
+}
@@ -0,0 +1,284 @@
+/// Integration tests for the embedded SQLite `ResultStore`.
+///
+/// Unlike `database_test.rs`'s PostgreSQL tests, these never need an
+/// external server or `DATABASE_URL` - `sqlite::memory:` gives each test its
+/// own throwaway database, so the schema's foreign keys, the unique
+/// (query, test) upsert, the pre-populated NIST test definitions and the
+/// analytics summary are exercised in CI with no setup at all.
+use chrono::{Duration, Utc};
+use randomnumbervalidator::storage::{connect, ResultStore, StorageConfig};
+use randomnumbervalidator::NistTestResult;
+use uuid::Uuid;
+
+async fn test_store() -> Box<dyn ResultStore> {
+    connect(StorageConfig::Sqlite("sqlite::memory:".to_string()))
+        .await
+        .expect("failed to connect to in-memory SQLite store")
+}
+
+fn sample_test_result(name: &str) -> NistTestResult {
+    NistTestResult {
+        name: name.to_string(),
+        passed: true,
+        p_value: 0.5,
+        p_values: vec![0.5, 0.6, 0.7],
+        description: "Test description".to_string(),
+        metrics: None,
+    }
+}
+
+#[tokio::test]
+async fn test_insert_query_without_test_results() {
+    let store = test_store().await;
+    let query_id = Uuid::new_v4();
+
+    store
+        .insert_query(
+            query_id,
+            "127.0.0.1",
+            "test-agent",
+            None,
+            "0,1,2,3",
+            false,
+            4,
+            32,
+            true,
+            0.75,
+            100,
+        )
+        .await
+        .expect("failed to insert query");
+
+    let rows = store
+        .join_results_for_query(query_id)
+        .await
+        .expect("failed to join results");
+    assert!(rows.is_empty());
+}
+
+#[tokio::test]
+async fn test_insert_test_result_round_trips_through_join() {
+    let store = test_store().await;
+    let query_id = Uuid::new_v4();
+
+    store
+        .insert_query(
+            query_id,
+            "127.0.0.1",
+            "test-agent",
+            None,
+            "0,1,2,3",
+            false,
+            4,
+            32,
+            true,
+            0.75,
+            100,
+        )
+        .await
+        .expect("failed to insert query");
+
+    let test_result = sample_test_result("Frequency (Monobit)");
+    store
+        .insert_test_result(query_id, &test_result)
+        .await
+        .expect("failed to insert test result");
+
+    let rows = store
+        .join_results_for_query(query_id)
+        .await
+        .expect("failed to join results");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].0, "Frequency (Monobit)");
+    assert!(rows[0].1);
+}
+
+#[tokio::test]
+async fn test_foreign_key_constraint_rejects_unknown_query() {
+    let store = test_store().await;
+    let fake_query_id = Uuid::new_v4();
+
+    let result = store
+        .insert_test_result(fake_query_id, &sample_test_result("Runs"))
+        .await;
+
+    assert!(
+        result.is_err(),
+        "inserting a test result for a non-existent query should fail"
+    );
+}
+
+#[tokio::test]
+async fn test_unique_constraint_updates_existing_result() {
+    let store = test_store().await;
+    let query_id = Uuid::new_v4();
+
+    store
+        .insert_query(
+            query_id,
+            "127.0.0.1",
+            "test-agent",
+            None,
+            "0,1,2,3",
+            false,
+            4,
+            32,
+            true,
+            0.75,
+            100,
+        )
+        .await
+        .expect("failed to insert query");
+
+    store
+        .insert_test_result(query_id, &sample_test_result("Runs"))
+        .await
+        .expect("failed to insert first test result");
+
+    // A second insert for the same (query, test) pair is an upsert, not a
+    // unique-constraint violation - the row gets updated in place.
+    let mut updated = sample_test_result("Runs");
+    updated.passed = false;
+    updated.p_value = 0.001;
+    store
+        .insert_test_result(query_id, &updated)
+        .await
+        .expect("failed to update existing test result");
+
+    let rows = store
+        .join_results_for_query(query_id)
+        .await
+        .expect("failed to join results");
+    assert_eq!(rows.len(), 1, "expected an update, not a duplicate row");
+    assert!(!rows[0].1);
+}
+
+#[tokio::test]
+async fn test_prepopulated_nist_tests() {
+    let store = test_store().await;
+    let query_id = Uuid::new_v4();
+
+    // Inserting a test result with a name that matches one of the seeded
+    // NIST test definitions should find (not duplicate) that row.
+    store
+        .insert_query(
+            query_id,
+            "127.0.0.1",
+            "test-agent",
+            None,
+            "0,1,2,3",
+            false,
+            4,
+            32,
+            true,
+            0.75,
+            100,
+        )
+        .await
+        .expect("failed to insert query");
+
+    store
+        .insert_test_result(query_id, &sample_test_result("Frequency (Monobit)"))
+        .await
+        .expect("failed to insert test result");
+    store
+        .insert_test_result(query_id, &sample_test_result("Runs"))
+        .await
+        .expect("failed to insert test result");
+
+    let rows = store
+        .join_results_for_query(query_id)
+        .await
+        .expect("failed to join results");
+    assert_eq!(rows.len(), 2);
+}
+
+#[tokio::test]
+async fn test_analytics_summary_aggregates_across_queries() {
+    let store = test_store().await;
+
+    for (quality_score, processing_time_ms, passed) in [(0.9, 100, true), (0.4, 300, false)] {
+        let query_id = Uuid::new_v4();
+        store
+            .insert_query(
+                query_id,
+                "127.0.0.1",
+                "test-agent",
+                None,
+                "0,1,2,3",
+                false,
+                4,
+                32,
+                passed,
+                quality_score,
+                processing_time_ms,
+            )
+            .await
+            .expect("failed to insert query");
+
+        let mut test_result = sample_test_result("Frequency (Monobit)");
+        test_result.passed = passed;
+        test_result.p_value = quality_score;
+        store
+            .insert_test_result(query_id, &test_result)
+            .await
+            .expect("failed to insert test result");
+    }
+
+    let window_start = Utc::now() - Duration::hours(1);
+    let window_end = Utc::now() + Duration::hours(1);
+    let summary = store
+        .analytics_summary(window_start, window_end)
+        .await
+        .expect("failed to compute analytics summary");
+
+    assert_eq!(summary.total_queries, 2);
+    assert_eq!(summary.nist_used_count, 2);
+    assert!((summary.mean_quality_score.unwrap() - 0.65).abs() < 1e-9);
+
+    let frequency_rate = summary
+        .test_pass_rates
+        .iter()
+        .find(|t| t.test_name == "Frequency (Monobit)")
+        .expect("Frequency (Monobit) should have a pass rate entry");
+    assert_eq!(frequency_rate.total, 2);
+    assert_eq!(frequency_rate.passed, 1);
+    assert!((frequency_rate.pass_rate - 0.5).abs() < 1e-9);
+
+    let bucket_count: i64 = summary.p_value_buckets.iter().map(|b| b.count).sum();
+    assert_eq!(bucket_count, 2);
+}
+
+#[tokio::test]
+async fn test_analytics_summary_empty_window_has_no_queries() {
+    let store = test_store().await;
+    let query_id = Uuid::new_v4();
+    store
+        .insert_query(
+            query_id,
+            "127.0.0.1",
+            "test-agent",
+            None,
+            "0,1,2,3",
+            false,
+            4,
+            32,
+            true,
+            0.9,
+            100,
+        )
+        .await
+        .expect("failed to insert query");
+
+    let far_future_start = Utc::now() + Duration::days(365);
+    let far_future_end = far_future_start + Duration::hours(1);
+    let summary = store
+        .analytics_summary(far_future_start, far_future_end)
+        .await
+        .expect("failed to compute analytics summary");
+
+    assert_eq!(summary.total_queries, 0);
+    assert_eq!(summary.mean_quality_score, None);
+    assert_eq!(summary.p50_quality_score, None);
+    assert!(summary.test_pass_rates.is_empty());
+}